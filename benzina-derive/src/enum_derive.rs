@@ -1,10 +1,15 @@
-use proc_macro2::{Span, TokenStream};
+use std::collections::HashMap;
+
+use proc_macro2::{Literal, Span, TokenStream};
 use quote::{ToTokens, TokenStreamExt, quote};
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "serde"))]
+use syn::LitByteStr;
 use syn::{
-    Data, DeriveInput, Fields, Ident, LitByteStr, LitStr, Path, Token, Type, spanned::Spanned,
+    Data, DeriveInput, Expr, ExprLit, ExprUnary, Fields, Ident, Lit, LitStr, Path, Token, Type,
+    UnOp, Variant, Visibility, parse::ParseStream, punctuated::Punctuated,
 };
 
-use crate::rename_rule::RenameRule;
+use crate::rename_rule::{Acronyms, RenameRule};
 
 macro_rules! fail {
     ($t:expr, $m:expr) => {
@@ -21,10 +26,21 @@ macro_rules! try_set {
     };
 }
 
+#[cfg_attr(
+    any(feature = "serde", feature = "clap"),
+    expect(
+        clippy::struct_excessive_bools,
+        reason = "the flags are independent attributes"
+    )
+)]
 pub(crate) struct Enum {
+    vis: Visibility,
     ident: Ident,
     sql_type: Type,
+    /// The rule used by PostgreSQL, and by the labels exposed to Rust code
     rename_all: RenameRule,
+    #[cfg(feature = "mysql")]
+    rename_all_mysql: RenameRule,
     variants: Vec<EnumVariant>,
 
     #[cfg(all(feature = "postgres", feature = "json"))]
@@ -34,6 +50,22 @@ pub(crate) struct Enum {
     #[cfg(all(feature = "postgres", feature = "json"))]
     data_column: Option<Ident>,
 
+    #[cfg(feature = "serde")]
+    serde: bool,
+    ordered: bool,
+    iter: bool,
+    labels: bool,
+    #[cfg(feature = "clap")]
+    clap: bool,
+    /// The integers stored by `transparent`, in declaration order
+    discriminants: Option<Vec<i32>>,
+    /// The name of the marker struct to define and of the PostgreSQL type
+    #[cfg(feature = "postgres")]
+    define_sql_type: Option<(Ident, LitStr)>,
+    /// The error built from the bytes of an unknown variant
+    #[cfg(any(feature = "postgres", feature = "mysql"))]
+    unknown_error: Option<Type>,
+
     crate_name: Option<Path>,
 }
 
@@ -41,9 +73,11 @@ struct EnumVariant {
     original_name: String,
     original_name_span: Span,
     rename: Option<String>,
+    acronyms: Acronyms,
     #[cfg(all(feature = "postgres", feature = "json"))]
     has_payload: bool,
 
+    #[cfg(any(feature = "postgres", feature = "mysql", feature = "serde"))]
     crate_name: Option<Path>,
 }
 
@@ -57,12 +91,24 @@ impl Enum {
         let mut first_attr = None;
         let mut sql_type = None;
         let mut rename_all = None;
+        let mut acronyms = None;
         #[cfg(all(feature = "postgres", feature = "json"))]
         let mut table = None;
         #[cfg(all(feature = "postgres", feature = "json"))]
         let mut column = None;
         #[cfg(all(feature = "postgres", feature = "json"))]
         let mut data_column = None;
+        #[cfg(feature = "serde")]
+        let mut serde = None;
+        let mut ordered = None;
+        let mut iter = None;
+        let mut labels = None;
+        #[cfg(feature = "clap")]
+        let mut clap = None;
+        let mut transparent = None;
+        #[cfg(feature = "postgres")]
+        let mut define_sql_type = None;
+        let mut unknown_error = None;
         let mut crate_name = None;
 
         for attr in input
@@ -75,13 +121,54 @@ impl Enum {
             attr.parse_nested_meta(|meta| {
                 if meta.path.is_ident("sql_type") {
                     meta.input.parse::<Token![=]>()?;
-                    let val: Type = meta.input.parse()?;
+                    // Older versions took the path as a string
+                    let val: Type = if meta.input.peek(LitStr) {
+                        meta.input.parse::<LitStr>()?.parse()?
+                    } else {
+                        meta.input.parse()?
+                    };
                     try_set!(sql_type, val, val);
                 } else if meta.path.is_ident("rename_all") {
+                    let parse_rule = |input: ParseStream<'_>| -> syn::Result<RenameRule> {
+                        input.parse::<Token![=]>()?;
+                        let val: LitStr = input.parse()?;
+                        val.value()
+                            .parse()
+                            .map_err(|err| syn::Error::new_spanned(val, err))
+                    };
+
+                    if meta.input.peek(Token![=]) {
+                        let rule = parse_rule(meta.input)?;
+                        try_set!(rename_all, (rule, rule), meta.path);
+                    } else {
+                        let mut pg = None;
+                        let mut mysql = None;
+                        meta.parse_nested_meta(|meta| {
+                            if meta.path.is_ident("pg") {
+                                try_set!(pg, parse_rule(meta.input)?, meta.path);
+                            } else if meta.path.is_ident("mysql") {
+                                try_set!(mysql, parse_rule(meta.input)?, meta.path);
+                            } else {
+                                fail!(meta.path, "expected `pg` or `mysql`");
+                            }
+
+                            Ok(())
+                        })?;
+                        // A missing rule would silently keep the variant names
+                        // on the other backend
+                        let (Some(pg), Some(mysql)) = (pg, mysql) else {
+                            fail!(
+                                meta.path,
+                                "`rename_all(...)` requires both a `pg` and a `mysql` rule, use `rename_all = \"...\"` to apply the same rule to both"
+                            );
+                        };
+                        try_set!(rename_all, (pg, mysql), meta.path);
+                    }
+                } else if meta.path.is_ident("acronyms") {
                     meta.input.parse::<Token![=]>()?;
                     let val: LitStr = meta.input.parse()?;
                     try_set!(
-                        rename_all,
+                        acronyms,
                         val.value()
                             .parse()
                             .map_err(|err| syn::Error::new_spanned(val, err))?,
@@ -123,6 +210,56 @@ impl Enum {
                         let _ = meta.input.parse::<Token![=]>()?;
                         let _: Ident = meta.input.parse()?;
                     }
+                } else if meta.path.is_ident("serde") {
+                    #[cfg(feature = "serde")]
+                    {
+                        try_set!(serde, true, meta.path);
+                    }
+                    #[cfg(not(feature = "serde"))]
+                    {
+                        fail!(
+                            meta.path,
+                            "`serde` requires the `serde` feature to be enabled"
+                        );
+                    }
+                } else if meta.path.is_ident("ordered") {
+                    try_set!(ordered, true, meta.path);
+                } else if meta.path.is_ident("iter") {
+                    try_set!(iter, true, meta.path);
+                } else if meta.path.is_ident("labels") {
+                    try_set!(labels, true, meta.path);
+                } else if meta.path.is_ident("clap") {
+                    #[cfg(feature = "clap")]
+                    {
+                        try_set!(clap, true, meta.path);
+                    }
+                    #[cfg(not(feature = "clap"))]
+                    {
+                        fail!(
+                            meta.path,
+                            "`clap` requires the `clap` feature to be enabled"
+                        );
+                    }
+                } else if meta.path.is_ident("transparent") {
+                    try_set!(transparent, true, meta.path);
+                } else if meta.path.is_ident("define_sql_type") {
+                    #[cfg(feature = "postgres")]
+                    {
+                        meta.input.parse::<Token![=]>()?;
+                        let val: LitStr = meta.input.parse()?;
+                        try_set!(define_sql_type, val.clone(), val);
+                    }
+                    #[cfg(not(feature = "postgres"))]
+                    {
+                        fail!(
+                            meta.path,
+                            "`define_sql_type` requires the `postgres` feature to be enabled"
+                        );
+                    }
+                } else if meta.path.is_ident("unknown_error") {
+                    meta.input.parse::<Token![=]>()?;
+                    let val: Type = meta.input.parse()?;
+                    try_set!(unknown_error, val, val);
                 } else if meta.path.is_ident("crate") {
                     meta.input.parse::<Token![=]>()?;
                     let val: Path = meta.input.parse()?;
@@ -141,7 +278,56 @@ impl Enum {
             fail!(first_attr, "expected `sql_type`");
         };
 
+        let discriminants = if transparent.unwrap_or(false) {
+            if unknown_error.is_some() {
+                fail!(
+                    first_attr,
+                    "`unknown_error` is not supported with `transparent`"
+                );
+            }
+            #[cfg(feature = "postgres")]
+            if define_sql_type.is_some() {
+                fail!(
+                    first_attr,
+                    "`define_sql_type` is not supported with `transparent`"
+                );
+            }
+            Some(parse_discriminants(&e.variants)?)
+        } else {
+            None
+        };
+
+        #[cfg(feature = "postgres")]
+        let define_sql_type = match define_sql_type {
+            Some(name) => {
+                let Some(ident) = (match &sql_type {
+                    Type::Path(path) if path.qself.is_none() => path.path.get_ident(),
+                    _ => None,
+                }) else {
+                    fail!(
+                        sql_type,
+                        "`define_sql_type` requires `sql_type` to be the name of the struct to define"
+                    );
+                };
+                Some((ident.clone(), name))
+            }
+            None => None,
+        };
+
+        let (rename_all, rename_all_mysql) = rename_all.unzip();
         let rename_all = rename_all.unwrap_or(RenameRule::None);
+        let rename_all_mysql = rename_all_mysql.unwrap_or(RenameRule::None);
+        // The rules of the disabled backends are only parsed to validate them.
+        #[cfg(not(feature = "mysql"))]
+        let _ = rename_all_mysql;
+        let acronyms = acronyms.unwrap_or(Acronyms::Group);
+        #[cfg(feature = "serde")]
+        let serde = serde.unwrap_or(false);
+        let ordered = ordered.unwrap_or(false);
+        let iter = iter.unwrap_or(false);
+        let labels = labels.unwrap_or(false);
+        #[cfg(feature = "clap")]
+        let clap = clap.unwrap_or(false);
 
         let variants = e
             .variants
@@ -167,6 +353,27 @@ impl Enum {
                     }
                 };
 
+                #[cfg(feature = "serde")]
+                if serde && has_payload {
+                    fail!(variant, "`serde` is only supported on enums without fields");
+                }
+                if ordered && has_payload {
+                    fail!(variant, "`ordered` is only supported on enums without fields");
+                }
+                if iter && has_payload {
+                    fail!(variant, "`iter` is only supported on enums without fields");
+                }
+                if labels && has_payload {
+                    fail!(variant, "`labels` is only supported on enums without fields");
+                }
+                if discriminants.is_some() && has_payload {
+                    fail!(variant, "`transparent` is only supported on enums without fields");
+                }
+                #[cfg(feature = "clap")]
+                if clap && has_payload {
+                    fail!(variant, "`clap` is only supported on enums without fields");
+                }
+
                 let name = variant.ident.to_string();
                 let mut rename = None;
 
@@ -191,22 +398,31 @@ impl Enum {
                 #[cfg(not(all(feature = "postgres", feature = "json")))]
                 let _ = has_payload;
 
-                let original_name_span = variant.span();
+                let original_name_span = variant.ident.span();
                 Ok(EnumVariant {
                     original_name: name,
                     original_name_span,
                     rename,
+                    acronyms,
                     #[cfg(all(feature = "postgres", feature = "json"))]
                     has_payload,
 
+                    #[cfg(any(feature = "postgres", feature = "mysql", feature = "serde"))]
                     crate_name: crate_name.clone(),
                 })
             })
             .collect::<Result<Vec<_>, syn::Error>>()?;
+        check_unique_labels(&variants, rename_all)?;
+        #[cfg(feature = "mysql")]
+        check_unique_labels(&variants, rename_all_mysql)?;
+
         Ok(Self {
+            vis: input.vis,
             ident: input.ident,
             sql_type,
             rename_all,
+            #[cfg(feature = "mysql")]
+            rename_all_mysql,
             variants,
 
             #[cfg(all(feature = "postgres", feature = "json"))]
@@ -216,6 +432,19 @@ impl Enum {
             #[cfg(all(feature = "postgres", feature = "json"))]
             data_column,
 
+            #[cfg(feature = "serde")]
+            serde,
+            ordered,
+            iter,
+            labels,
+            #[cfg(feature = "clap")]
+            clap,
+            discriminants,
+            #[cfg(feature = "postgres")]
+            define_sql_type,
+            #[cfg(any(feature = "postgres", feature = "mysql"))]
+            unknown_error,
+
             crate_name,
         })
     }
@@ -225,7 +454,10 @@ impl Enum {
         self.variants.iter().any(|variant| variant.has_payload)
     }
 
-    #[cfg(not(all(feature = "postgres", feature = "json")))]
+    #[cfg(all(
+        any(feature = "postgres", feature = "mysql"),
+        not(all(feature = "postgres", feature = "json"))
+    ))]
     #[expect(
         clippy::unused_self,
         reason = "kept for compatibility with the above implementation"
@@ -233,15 +465,27 @@ impl Enum {
     fn has_json_fields(&self) -> bool {
         false
     }
+
+    /// The labels exposed to Rust code, which always follow the `pg` rule so
+    /// that enabling a backend never changes them
+    fn labels(&self) -> impl Iterator<Item = LitStr> {
+        let rename_rule = self.rename_all;
+        self.variants.iter().map(move |variant| {
+            LitStr::new(&variant.label(rename_rule), variant.original_name_span)
+        })
+    }
 }
 
 impl ToTokens for Enum {
     #[expect(clippy::too_many_lines)]
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let Self {
+            vis,
             ident,
             sql_type,
-            rename_all,
+            rename_all: _,
+            #[cfg(feature = "mysql")]
+                rename_all_mysql: _,
             variants,
 
             #[cfg(all(feature = "postgres", feature = "json"))]
@@ -251,14 +495,60 @@ impl ToTokens for Enum {
             #[cfg(all(feature = "postgres", feature = "json"))]
                 data_column: _,
 
+            #[cfg(feature = "serde")]
+                serde: _,
+            ordered,
+            iter,
+            labels,
+            #[cfg(feature = "clap")]
+                clap: _,
+            discriminants,
+            #[cfg(feature = "postgres")]
+                define_sql_type: _,
+            #[cfg(any(feature = "postgres", feature = "mysql"))]
+            unknown_error,
+
             crate_name,
         } = &self;
         let crate_name = crate::crate_name(crate_name);
 
+        #[cfg(any(feature = "postgres", feature = "mysql"))]
+        let unknown_variant_arm = if let Some(unknown_error) = unknown_error {
+            quote! {
+                unknown => {
+                    #crate_name::__private::std::result::Result::Err(
+                        #crate_name::__private::std::convert::Into::into(
+                            <#unknown_error as #crate_name::__private::std::convert::From<
+                                #crate_name::__private::std::vec::Vec<u8>
+                            >>::from(
+                                <[u8] as #crate_name::__private::std::borrow::ToOwned>::to_owned(unknown)
+                            )
+                        )
+                    )
+                },
+            }
+        } else {
+            quote! {
+                _ => {
+                    #crate_name::__private::std::result::Result::Err(
+                        #crate_name::__private::std::convert::Into::into(
+                            "Unrecognized enum variant"
+                        )
+                    )
+                },
+            }
+        };
+
+        #[cfg(feature = "postgres")]
         let has_json_fields = self.has_json_fields();
+        #[cfg(all(feature = "postgres", feature = "json"))]
         let impls_ident = Ident::new(&format!("{ident}Kind"), ident.span());
 
-        let as_expression = quote! {
+        // Like diesel's `AsExpression` derive, also allow comparing against
+        // nullable columns
+        let nullable_sql_type =
+            quote! { #crate_name::__private::diesel::sql_types::Nullable<#sql_type> };
+        let as_expression = [quote! { #sql_type }, nullable_sql_type].map(|sql_type| quote! {
             #[automatically_derived]
             impl #crate_name::__private::diesel::expression::AsExpression<#sql_type> for #ident {
                 type Expression = #crate_name::__private::diesel::internal::derives::as_expression::Bound<
@@ -294,16 +584,21 @@ impl ToTokens for Enum {
                     #crate_name::__private::diesel::internal::derives::as_expression::Bound::new(self)
                 }
             }
-        };
+        });
 
+        #[cfg(feature = "postgres")]
         let from_bytes_arms = variants
             .iter()
-            .map(|variant| variant.gen_from_bytes(has_json_fields, *rename_all))
+            .map(|variant| variant.gen_from_bytes(has_json_fields, self.rename_all))
             .collect::<Vec<_>>();
+        // A `match` over the labels rather than an index into a `&[&[u8]]`:
+        // the `enum_derive` benchmark collecting the binds of 1M rows shows
+        // no difference between the two. Both backends copy the label into
+        // an owned bind buffer, so there is no borrowed `set_value` path.
         #[cfg(feature = "postgres")]
         let to_byte_str_arms = variants
             .iter()
-            .map(|variant| variant.gen_to_byte_str(has_json_fields, *rename_all))
+            .map(|variant| variant.gen_to_byte_str(has_json_fields, self.rename_all))
             .collect::<Vec<_>>();
 
         #[cfg(feature = "postgres")]
@@ -335,29 +630,37 @@ impl ToTokens for Enum {
             )
         };
 
+        #[cfg(all(feature = "postgres", feature = "array"))]
+        let array_element = quote! {
+            #[automatically_derived]
+            impl #crate_name::ArrayElement for #ident {
+                type SqlType = #sql_type;
+            }
+        };
+        #[cfg(all(feature = "postgres", not(feature = "array")))]
+        let array_element = quote! {};
+
         #[cfg(feature = "postgres")]
         let postgres_from_to_sql = if has_json_fields {
             quote! {}
         } else {
             quote! {
+                #array_element
+
                 #[automatically_derived]
                 impl #crate_name::__private::diesel::deserialize::FromSql<#sql_type, #crate_name::__private::diesel::pg::Pg> for #ident {
+                    #[inline]
                     fn from_sql(bytes: #crate_name::__private::diesel::pg::PgValue<'_>) -> #crate_name::__private::diesel::deserialize::Result<Self> {
                         match bytes.as_bytes() {
                             #(#from_bytes_arms)*
-                            _ => {
-                                #crate_name::__private::std::result::Result::Err(
-                                    #crate_name::__private::std::convert::Into::into(
-                                        "Unrecognized enum variant"
-                                    )
-                                )
-                            },
+                            #unknown_variant_arm
                         }
                     }
                 }
 
                 #[automatically_derived]
                 impl #crate_name::__private::diesel::serialize::ToSql<#sql_type, #crate_name::__private::diesel::pg::Pg> for #ident {
+                    #[inline]
                     fn to_sql<'b>(&'b self, out: &mut #crate_name::__private::diesel::serialize::Output<'b, '_, #crate_name::__private::diesel::pg::Pg>) -> #crate_name::__private::diesel::serialize::Result {
                         let s: &[u8] = match self {
                             #(#to_byte_str_arms)*
@@ -372,8 +675,31 @@ impl ToTokens for Enum {
             }
         };
 
+        #[cfg(feature = "postgres")]
+        let sql_type_definition = match &self.define_sql_type {
+            Some((sql_type_ident, name)) => {
+                let doc = format!("The PostgreSQL `{}` type of [`{ident}`]", name.value());
+                quote! {
+                    #[doc = #doc]
+                    #[derive(
+                        #crate_name::__private::std::fmt::Debug,
+                        #crate_name::__private::std::clone::Clone,
+                        #crate_name::__private::std::marker::Copy,
+                        #crate_name::__private::std::default::Default,
+                        #crate_name::__private::diesel::query_builder::QueryId,
+                        #crate_name::__private::diesel::sql_types::SqlType,
+                    )]
+                    #[diesel(postgres_type(name = #name))]
+                    #vis struct #sql_type_ident;
+                }
+            }
+            None => quote! {},
+        };
+
         #[cfg(feature = "postgres")]
         let postgres = quote! {
+            #sql_type_definition
+
             #[automatically_derived]
             impl #crate_name::__private::diesel::deserialize::Queryable<#queryable_sql_type, #crate_name::__private::diesel::pg::Pg> for #ident {
                 type Row = #queryable_row_type;
@@ -398,9 +724,12 @@ impl ToTokens for Enum {
             });
 
             let impls_enum = Self {
+                vis: syn::parse_quote!(pub),
                 ident: impls_ident.clone(),
                 sql_type: self.sql_type.clone(),
                 rename_all: self.rename_all,
+                #[cfg(feature = "mysql")]
+                rename_all_mysql: self.rename_all_mysql,
                 variants: self
                     .variants
                     .iter()
@@ -409,12 +738,14 @@ impl ToTokens for Enum {
                              original_name,
                              original_name_span,
                              rename,
+                             acronyms,
                              has_payload: _,
                              crate_name,
                          }| EnumVariant {
                             original_name: original_name.clone(),
                             original_name_span: *original_name_span,
                             rename: rename.clone(),
+                            acronyms: *acronyms,
                             has_payload: false,
                             crate_name: crate_name.clone(),
                         },
@@ -423,6 +754,16 @@ impl ToTokens for Enum {
                 table: None,
                 column: None,
                 data_column: None,
+                #[cfg(feature = "serde")]
+                serde: false,
+                ordered: false,
+                iter: false,
+                labels: false,
+                #[cfg(feature = "clap")]
+                clap: false,
+                discriminants: None,
+                define_sql_type: None,
+                unknown_error: self.unknown_error.clone(),
                 crate_name: self.crate_name.clone(),
             };
             let selectable_insertable_impl = if let (Some(table), Some(column), Some(data_column)) =
@@ -500,11 +841,11 @@ impl ToTokens for Enum {
         } else {
             let from_bytes_arms = variants
                 .iter()
-                .map(|variant| variant.gen_from_bytes(false, *rename_all))
+                .map(|variant| variant.gen_from_bytes(false, self.rename_all_mysql))
                 .collect::<Vec<_>>();
             let to_byte_str_arms = variants
                 .iter()
-                .map(|variant| variant.gen_to_byte_str(false, *rename_all))
+                .map(|variant| variant.gen_to_byte_str(false, self.rename_all_mysql))
                 .collect::<Vec<_>>();
 
             quote! {
@@ -519,22 +860,18 @@ impl ToTokens for Enum {
 
                 #[automatically_derived]
                 impl #crate_name::__private::diesel::deserialize::FromSql<#sql_type, #crate_name::__private::diesel::mysql::Mysql> for #ident {
+                    #[inline]
                     fn from_sql(bytes: #crate_name::__private::diesel::mysql::MysqlValue<'_>) -> #crate_name::__private::diesel::deserialize::Result<Self> {
                         match bytes.as_bytes() {
                             #(#from_bytes_arms)*
-                            _ => {
-                                #crate_name::__private::std::result::Result::Err(
-                                    #crate_name::__private::std::convert::Into::into(
-                                        "Unrecognized enum variant"
-                                    )
-                                )
-                            },
+                            #unknown_variant_arm
                         }
                     }
                 }
 
                 #[automatically_derived]
                 impl #crate_name::__private::diesel::serialize::ToSql<#sql_type, #crate_name::__private::diesel::mysql::Mysql> for #ident {
+                    #[inline]
                     fn to_sql<'b>(&'b self, out: &mut #crate_name::__private::diesel::serialize::Output<'b, '_, #crate_name::__private::diesel::mysql::Mysql>) -> #crate_name::__private::diesel::serialize::Result {
                         let s: &[u8] = match self {
                             #(#to_byte_str_arms)*
@@ -549,25 +886,557 @@ impl ToTokens for Enum {
         #[cfg(not(feature = "mysql"))]
         let mysql = quote! {};
 
+        #[cfg(feature = "serde")]
+        let serde = if self.serde {
+            let rename_rule = self.rename_all;
+            let serialize_arms = variants
+                .iter()
+                .map(|variant| variant.gen_serialize(rename_rule));
+            let deserialize_str_arms = variants
+                .iter()
+                .map(|variant| variant.gen_deserialize_str(ident, rename_rule));
+            let deserialize_bytes_arms = variants
+                .iter()
+                .map(|variant| variant.gen_deserialize_bytes(ident, rename_rule));
+            let labels = self.labels();
+            let expecting = LitStr::new(&format!("a `{ident}` variant"), ident.span());
+
+            quote! {
+                #[automatically_derived]
+                impl #crate_name::__private::serde_core::Serialize for #ident {
+                    fn serialize<__S>(&self, serializer: __S) -> #crate_name::__private::std::result::Result<__S::Ok, __S::Error>
+                    where
+                        __S: #crate_name::__private::serde_core::Serializer,
+                    {
+                        let s: &'static str = match self {
+                            #(#serialize_arms)*
+                        };
+                        serializer.serialize_str(s)
+                    }
+                }
+
+                #[automatically_derived]
+                impl<'de> #crate_name::__private::serde_core::Deserialize<'de> for #ident {
+                    fn deserialize<__D>(deserializer: __D) -> #crate_name::__private::std::result::Result<Self, __D::Error>
+                    where
+                        __D: #crate_name::__private::serde_core::Deserializer<'de>,
+                    {
+                        const VARIANTS: &[&str] = &[#(#labels),*];
+
+                        struct __Visitor;
+
+                        impl #crate_name::__private::serde_core::de::Visitor<'_> for __Visitor {
+                            type Value = #ident;
+
+                            fn expecting(&self, f: &mut #crate_name::__private::std::fmt::Formatter<'_>) -> #crate_name::__private::std::fmt::Result {
+                                f.write_str(#expecting)
+                            }
+
+                            fn visit_str<__E>(self, value: &str) -> #crate_name::__private::std::result::Result<Self::Value, __E>
+                            where
+                                __E: #crate_name::__private::serde_core::de::Error,
+                            {
+                                match value {
+                                    #(#deserialize_str_arms)*
+                                    _ => #crate_name::__private::std::result::Result::Err(
+                                        __E::unknown_variant(value, VARIANTS)
+                                    ),
+                                }
+                            }
+
+                            fn visit_bytes<__E>(self, value: &[u8]) -> #crate_name::__private::std::result::Result<Self::Value, __E>
+                            where
+                                __E: #crate_name::__private::serde_core::de::Error,
+                            {
+                                match value {
+                                    #(#deserialize_bytes_arms)*
+                                    _ => #crate_name::__private::std::result::Result::Err(
+                                        __E::invalid_value(
+                                            #crate_name::__private::serde_core::de::Unexpected::Bytes(value),
+                                            &self,
+                                        )
+                                    ),
+                                }
+                            }
+                        }
+
+                        deserializer.deserialize_str(__Visitor)
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+        #[cfg(not(feature = "serde"))]
+        let serde = quote! {};
+
+        let ordered = if *ordered {
+            let index_arms = variants.iter().enumerate().map(|(index, variant)| {
+                let original_name_ident = variant.original_name();
+                quote! {
+                    Self::#original_name_ident => #index,
+                }
+            });
+
+            quote! {
+                #[automatically_derived]
+                impl #ident {
+                    /// Returns the position of the variant in the enum declaration.
+                    #[must_use]
+                    #[inline]
+                    #vis const fn as_index(&self) -> usize {
+                        match self {
+                            #(#index_arms)*
+                        }
+                    }
+                }
+
+                #[automatically_derived]
+                impl #crate_name::__private::std::cmp::PartialOrd for #ident {
+                    fn partial_cmp(&self, other: &Self) -> #crate_name::__private::std::option::Option<#crate_name::__private::std::cmp::Ordering> {
+                        #crate_name::__private::std::option::Option::Some(
+                            #crate_name::__private::std::cmp::Ord::cmp(self, other)
+                        )
+                    }
+                }
+
+                #[automatically_derived]
+                impl #crate_name::__private::std::cmp::Ord for #ident {
+                    fn cmp(&self, other: &Self) -> #crate_name::__private::std::cmp::Ordering {
+                        #crate_name::__private::std::cmp::Ord::cmp(&self.as_index(), &other.as_index())
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        let iter = if *iter {
+            let iter_ident = Ident::new(&format!("{ident}Iter"), ident.span());
+            let iter_doc = format!("An iterator over the variants of [`{ident}`]");
+            let len = variants.len();
+            let get_arms = variants.iter().enumerate().map(|(index, variant)| {
+                let original_name_ident = variant.original_name();
+                quote! {
+                    #index => #crate_name::__private::std::option::Option::Some(#ident::#original_name_ident),
+                }
+            });
+
+            quote! {
+                #[doc = #iter_doc]
+                #[derive(#crate_name::__private::std::fmt::Debug, #crate_name::__private::std::clone::Clone)]
+                #vis struct #iter_ident {
+                    front: usize,
+                    back: usize,
+                }
+
+                #[automatically_derived]
+                impl #iter_ident {
+                    fn get(index: usize) -> #crate_name::__private::std::option::Option<#ident> {
+                        match index {
+                            #(#get_arms)*
+                            _ => #crate_name::__private::std::option::Option::None,
+                        }
+                    }
+                }
+
+                #[automatically_derived]
+                impl #crate_name::__private::std::iter::Iterator for #iter_ident {
+                    type Item = #ident;
+
+                    fn next(&mut self) -> #crate_name::__private::std::option::Option<Self::Item> {
+                        if self.front >= self.back {
+                            return #crate_name::__private::std::option::Option::None;
+                        }
+                        self.front += 1;
+                        Self::get(self.front - 1)
+                    }
+
+                    fn size_hint(&self) -> (usize, #crate_name::__private::std::option::Option<usize>) {
+                        let len = self.back.saturating_sub(self.front);
+                        (len, #crate_name::__private::std::option::Option::Some(len))
+                    }
+                }
+
+                #[automatically_derived]
+                impl #crate_name::__private::std::iter::DoubleEndedIterator for #iter_ident {
+                    fn next_back(&mut self) -> #crate_name::__private::std::option::Option<Self::Item> {
+                        if self.front >= self.back {
+                            return #crate_name::__private::std::option::Option::None;
+                        }
+                        self.back -= 1;
+                        Self::get(self.back)
+                    }
+                }
+
+                #[automatically_derived]
+                impl #crate_name::__private::std::iter::ExactSizeIterator for #iter_ident {}
+
+                #[automatically_derived]
+                impl #crate_name::__private::std::iter::FusedIterator for #iter_ident {}
+
+                #[automatically_derived]
+                impl #ident {
+                    /// Returns an iterator over the variants in declaration order.
+                    #[must_use]
+                    #vis const fn variants() -> #iter_ident {
+                        #iter_ident {
+                            front: 0,
+                            back: #len,
+                        }
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        let labels = if *labels {
+            let labels = self.labels();
+            // `as_str`, `Display` and `FromStr` use the labels written to
+            // PostgreSQL: with `rename_all(pg = ..., mysql = ...)` the labels
+            // written to MySQL follow a different rule
+            let as_str_arms = variants.iter().zip(self.labels()).map(|(variant, label)| {
+                let original_name_ident = variant.original_name();
+                quote! {
+                    Self::#original_name_ident => #label,
+                }
+            });
+            let from_str_arms = variants.iter().zip(self.labels()).map(|(variant, label)| {
+                let original_name_ident = variant.original_name();
+                quote! {
+                    #label => #crate_name::__private::std::result::Result::Ok(Self::#original_name_ident),
+                }
+            });
+            quote! {
+                #[automatically_derived]
+                impl #ident {
+                    /// The labels of the variants in declaration order
+                    #vis const VARIANTS: &'static [&'static str] = &[#(#labels),*];
+
+                    /// The label of the variant
+                    #[must_use]
+                    #[inline]
+                    #vis const fn as_str(&self) -> &'static str {
+                        match self {
+                            #(#as_str_arms)*
+                        }
+                    }
+                }
+
+                #[automatically_derived]
+                impl #crate_name::__private::std::fmt::Display for #ident {
+                    fn fmt(&self, f: &mut #crate_name::__private::std::fmt::Formatter<'_>) -> #crate_name::__private::std::fmt::Result {
+                        f.write_str(self.as_str())
+                    }
+                }
+
+                #[automatically_derived]
+                impl #crate_name::__private::std::str::FromStr for #ident {
+                    type Err = #crate_name::error::UnknownVariant;
+
+                    fn from_str(s: &str) -> #crate_name::__private::std::result::Result<Self, Self::Err> {
+                        match s {
+                            #(#from_str_arms)*
+                            _ => #crate_name::__private::std::result::Result::Err(
+                                #crate_name::__private::unknown_variant(s, Self::VARIANTS)
+                            ),
+                        }
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        // Unlike `as_str` this doesn't need `labels`, so that every enum
+        // without fields can be passed to APIs taking an `Into<&str>`
+        #[cfg(all(feature = "postgres", feature = "json"))]
+        let has_fields = self.has_json_fields();
+        #[cfg(not(all(feature = "postgres", feature = "json")))]
+        let has_fields = false;
+        let into_str = if has_fields {
+            quote! {}
+        } else {
+            let into_str_arms = variants.iter().zip(self.labels()).map(|(variant, label)| {
+                let original_name_ident = variant.original_name();
+                quote! {
+                    #ident::#original_name_ident => #label,
+                }
+            });
+            quote! {
+                #[automatically_derived]
+                impl #crate_name::__private::std::convert::From<&#ident> for &'static str {
+                    #[inline]
+                    fn from(value: &#ident) -> Self {
+                        match value {
+                            #(#into_str_arms)*
+                        }
+                    }
+                }
+            }
+        };
+
+        #[cfg(feature = "clap")]
+        let clap = if self.clap {
+            let value_variants = variants.iter().map(|variant| {
+                let original_name_ident = variant.original_name();
+                quote! { Self::#original_name_ident }
+            });
+            let possible_value_arms = variants.iter().zip(self.labels()).map(|(variant, label)| {
+                let original_name_ident = variant.original_name();
+                quote! {
+                    Self::#original_name_ident => #label,
+                }
+            });
+
+            quote! {
+                #[automatically_derived]
+                impl #crate_name::__private::clap::ValueEnum for #ident {
+                    fn value_variants<'a>() -> &'a [Self] {
+                        &[#(#value_variants),*]
+                    }
+
+                    fn to_possible_value(&self) -> #crate_name::__private::std::option::Option<#crate_name::__private::clap::builder::PossibleValue> {
+                        let label: &'static str = match self {
+                            #(#possible_value_arms)*
+                        };
+                        #crate_name::__private::std::option::Option::Some(
+                            #crate_name::__private::clap::builder::PossibleValue::new(label)
+                        )
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+        #[cfg(not(feature = "clap"))]
+        let clap = quote! {};
+
+        // The text representation of the backends would conflict with the
+        // integer one
+        let (postgres, mysql, transparent) = if let Some(discriminants) = discriminants {
+            let from_integer_arms = variants.iter().zip(discriminants).map(|(variant, discriminant)| {
+                let original_name_ident = variant.original_name();
+                let discriminant = Literal::i32_unsuffixed(*discriminant);
+                quote! {
+                    #discriminant => #crate_name::__private::std::result::Result::Ok(Self::#original_name_ident),
+                }
+            });
+            let to_integer_arms =
+                variants
+                    .iter()
+                    .zip(discriminants)
+                    .map(|(variant, discriminant)| {
+                        let original_name_ident = variant.original_name();
+                        let discriminant = Literal::i32_unsuffixed(*discriminant);
+                        quote! {
+                            Self::#original_name_ident => &#discriminant,
+                        }
+                    });
+
+            let into_integer_arms =
+                variants
+                    .iter()
+                    .zip(discriminants)
+                    .map(|(variant, discriminant)| {
+                        let original_name_ident = variant.original_name();
+                        let discriminant = Literal::i32_unsuffixed(*discriminant);
+                        quote! {
+                            #ident::#original_name_ident => #discriminant,
+                        }
+                    });
+
+            // `i16` is only implemented when it can hold every discriminant
+            let into_i16 =
+                discriminants
+                    .iter()
+                    .map(|&discriminant| i16::try_from(discriminant))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_or_else(
+                        |_| quote! {},
+                        |discriminants| {
+                            let into_i16_arms = variants.iter().zip(discriminants).map(
+                                |(variant, discriminant)| {
+                                    let original_name_ident = variant.original_name();
+                                    let discriminant = Literal::i16_unsuffixed(discriminant);
+                                    quote! {
+                                        #ident::#original_name_ident => #discriminant,
+                                    }
+                                },
+                            );
+                            quote! {
+                                #[automatically_derived]
+                                impl #crate_name::__private::std::convert::From<&#ident> for i16 {
+                                    #[inline]
+                                    fn from(value: &#ident) -> Self {
+                                        match value {
+                                            #(#into_i16_arms)*
+                                        }
+                                    }
+                                }
+                            }
+                        },
+                    );
+
+            let transparent = quote! {
+                #[automatically_derived]
+                impl #crate_name::__private::std::convert::From<&#ident> for i32 {
+                    #[inline]
+                    fn from(value: &#ident) -> Self {
+                        match value {
+                            #(#into_integer_arms)*
+                        }
+                    }
+                }
+
+                #into_i16
+
+                #[automatically_derived]
+                impl<__DB> #crate_name::__private::diesel::deserialize::Queryable<#sql_type, __DB> for #ident
+                where
+                    __DB: #crate_name::__private::diesel::backend::Backend,
+                    Self: #crate_name::__private::diesel::deserialize::FromSql<#sql_type, __DB>,
+                {
+                    type Row = Self;
+
+                    fn build(row: Self::Row) -> #crate_name::__private::diesel::deserialize::Result<Self> {
+                        #crate_name::__private::std::result::Result::Ok(row)
+                    }
+                }
+
+                #[automatically_derived]
+                impl<__DB> #crate_name::__private::diesel::deserialize::FromSql<#sql_type, __DB> for #ident
+                where
+                    __DB: #crate_name::__private::diesel::backend::Backend,
+                    i32: #crate_name::__private::diesel::deserialize::FromSql<#sql_type, __DB>,
+                {
+                    #[inline]
+                    fn from_sql(bytes: <__DB as #crate_name::__private::diesel::backend::Backend>::RawValue<'_>) -> #crate_name::__private::diesel::deserialize::Result<Self> {
+                        match <i32 as #crate_name::__private::diesel::deserialize::FromSql<#sql_type, __DB>>::from_sql(bytes)? {
+                            #(#from_integer_arms)*
+                            _ => #crate_name::__private::std::result::Result::Err(
+                                #crate_name::__private::std::convert::Into::into("Unrecognized enum variant")
+                            ),
+                        }
+                    }
+                }
+
+                #[automatically_derived]
+                impl<__DB> #crate_name::__private::diesel::serialize::ToSql<#sql_type, __DB> for #ident
+                where
+                    __DB: #crate_name::__private::diesel::backend::Backend,
+                    i32: #crate_name::__private::diesel::serialize::ToSql<#sql_type, __DB>,
+                {
+                    #[inline]
+                    fn to_sql<'b>(&'b self, out: &mut #crate_name::__private::diesel::serialize::Output<'b, '_, __DB>) -> #crate_name::__private::diesel::serialize::Result {
+                        let value: &'static i32 = match self {
+                            #(#to_integer_arms)*
+                        };
+                        <i32 as #crate_name::__private::diesel::serialize::ToSql<#sql_type, __DB>>::to_sql(value, out)
+                    }
+                }
+            };
+            (quote! {}, quote! {}, transparent)
+        } else {
+            (postgres, mysql, quote! {})
+        };
+
         tokens.append_all(quote! {
-            #as_expression
+            #(#as_expression)*
             #postgres
             #postgres_extra
             #mysql
+            #transparent
+            #serde
+            #ordered
+            #iter
+            #labels
+            #into_str
+            #clap
         });
     }
 }
 
+/// Computes the discriminants of the variants like the compiler does, starting
+/// from zero and incrementing the previous one when it is not explicit
+fn parse_discriminants(variants: &Punctuated<Variant, Token![,]>) -> syn::Result<Vec<i32>> {
+    let mut next = Some(0i32);
+    variants
+        .iter()
+        .map(|variant| {
+            let discriminant = match &variant.discriminant {
+                Some((_eq, expr)) => parse_discriminant(expr)?,
+                None => match next {
+                    Some(discriminant) => discriminant,
+                    None => fail!(variant, "the discriminant doesn't fit in an `i32`"),
+                },
+            };
+            next = discriminant.checked_add(1);
+            Ok(discriminant)
+        })
+        .collect()
+}
+
+fn parse_discriminant(expr: &Expr) -> syn::Result<i32> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse(),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr: inner,
+            ..
+        }) => match &**inner {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(int), ..
+            }) => format!("-{}", int.base10_digits()).parse().map_err(|_| {
+                syn::Error::new_spanned(expr, "the discriminant doesn't fit in an `i32`")
+            }),
+            _ => fail!(expr, "`transparent` requires integer literal discriminants"),
+        },
+        _ => fail!(expr, "`transparent` requires integer literal discriminants"),
+    }
+}
+
+/// Fails if two variants end up with the same label, which would make the
+/// conversion from the label ambiguous
+fn check_unique_labels(variants: &[EnumVariant], rename_rule: RenameRule) -> syn::Result<()> {
+    let mut labels = HashMap::with_capacity(variants.len());
+    for variant in variants {
+        let label = variant.label(rename_rule);
+        if let Some(previous) = labels.insert(label.clone(), &variant.original_name) {
+            return Err(syn::Error::new(
+                variant.original_name_span,
+                format!(
+                    "`{}` has the same label `{label}` as `{previous}`",
+                    variant.original_name
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
 impl EnumVariant {
     fn original_name(&self) -> Ident {
         Ident::new(&self.original_name, self.original_name_span)
     }
 
+    /// The label used to represent the variant outside of Rust
+    fn label(&self, rename_rule: RenameRule) -> String {
+        self.rename
+            .clone()
+            .unwrap_or_else(|| rename_rule.format(&self.original_name, self.acronyms))
+    }
+
+    #[cfg(any(feature = "postgres", feature = "mysql"))]
     fn gen_from_bytes(&self, _has_fields: bool, rename_rule: RenameRule) -> impl ToTokens {
         let Self {
-            original_name,
+            original_name: _,
             original_name_span,
-            rename,
+            rename: _,
+            acronyms: _,
             #[cfg(all(feature = "postgres", feature = "json"))]
                 has_payload: _,
 
@@ -575,9 +1444,7 @@ impl EnumVariant {
         } = self;
         let crate_name = crate::crate_name(crate_name);
 
-        let rename = rename
-            .clone()
-            .unwrap_or_else(|| rename_rule.format(original_name));
+        let rename = self.label(rename_rule);
 
         let original_name_ident = self.original_name();
         let rename_bytes = LitByteStr::new(rename.as_bytes(), *original_name_span);
@@ -611,20 +1478,20 @@ impl EnumVariant {
         }
     }
 
+    #[cfg(any(feature = "postgres", feature = "mysql"))]
     fn gen_to_byte_str(&self, _has_fields: bool, rename_rule: RenameRule) -> impl ToTokens {
         let Self {
-            original_name,
+            original_name: _,
             original_name_span,
-            rename,
+            rename: _,
+            acronyms: _,
             #[cfg(all(feature = "postgres", feature = "json"))]
                 has_payload: _,
 
             crate_name: _,
         } = self;
 
-        let rename = rename
-            .clone()
-            .unwrap_or_else(|| rename_rule.format(original_name));
+        let rename = self.label(rename_rule);
 
         let original_name_ident = self.original_name();
         let rename_bytes = LitByteStr::new(rename.as_bytes(), *original_name_span);
@@ -633,6 +1500,35 @@ impl EnumVariant {
         }
     }
 
+    #[cfg(feature = "serde")]
+    fn gen_serialize(&self, rename_rule: RenameRule) -> impl ToTokens {
+        let original_name_ident = self.original_name();
+        let label = LitStr::new(&self.label(rename_rule), self.original_name_span);
+        quote! {
+            Self::#original_name_ident => #label,
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    fn gen_deserialize_str(&self, ident: &Ident, rename_rule: RenameRule) -> impl ToTokens {
+        let crate_name = crate::crate_name(&self.crate_name);
+        let original_name_ident = self.original_name();
+        let label = LitStr::new(&self.label(rename_rule), self.original_name_span);
+        quote! {
+            #label => #crate_name::__private::std::result::Result::Ok(#ident::#original_name_ident),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    fn gen_deserialize_bytes(&self, ident: &Ident, rename_rule: RenameRule) -> impl ToTokens {
+        let crate_name = crate::crate_name(&self.crate_name);
+        let original_name_ident = self.original_name();
+        let label = LitByteStr::new(self.label(rename_rule).as_bytes(), self.original_name_span);
+        quote! {
+            #label => #crate_name::__private::std::result::Result::Ok(#ident::#original_name_ident),
+        }
+    }
+
     #[cfg(all(feature = "postgres", feature = "json"))]
     fn gen_to_insertable(&self, ident: &Ident, impls_ident: &Ident) -> impl ToTokens {
         let crate_name = crate::crate_name(&self.crate_name);
@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 
 use proc_macro2::{Span, TokenStream};
 use quote::{ToTokens, quote};
-use syn::{Ident, Index, Token, punctuated::Punctuated};
+use syn::{Ident, Index, Member, Path, Token, Type, punctuated::Punctuated};
 
 use self::{
     quantity::Quantity,
@@ -16,7 +16,14 @@ mod utils;
 
 pub(crate) struct Join {
     input: Ident,
+    /// The path to benzina, if it isn't available as `::benzina`
+    crate_name: Option<Path>,
+    /// The `BuildHasher` of the maps, instead of the one picked by the features
+    hasher: Option<Type>,
     transformation: Transformation,
+    sorted: bool,
+    streamed: bool,
+    grouped: bool,
 }
 
 pub(super) enum NestedOrNot {
@@ -26,26 +33,90 @@ pub(super) enum NestedOrNot {
 
 pub(super) struct Transformation {
     quantity: Quantity,
-    output_type: Ident,
-    entries: Punctuated<(Ident, NestedOrNot), Token![,]>,
+    /// Always written with a turbofish, so that it works in expressions too
+    output_type: Path,
+    entries: Punctuated<(Field, NestedOrNot), Token![,]>,
+}
+
+pub(super) enum Field {
+    Member(Member),
+    /// `..`, the value is the base of the struct update syntax
+    Base(Token![..]),
 }
 
 pub(super) struct NoTransformation {
     quantity: Quantity,
     tuple_index: usize,
+    /// Whether other fields read the same tuple index, in which case the
+    /// value has to be cloned instead of moved out of the row
+    shared: bool,
 }
 
 impl Join {
+    /// Assumes that the input rows are grouped by the id of the outermost
+    /// entity, so that its groups can be built without a map.
+    pub(crate) fn sorted(self) -> Self {
+        Self {
+            sorted: true,
+            ..self
+        }
+    }
+
+    /// Returns an iterator over the outermost entities instead of collecting
+    /// them, which must therefore be a `Vec` or a `Vec0`.
+    pub(crate) fn streamed(self) -> syn::Result<Self> {
+        if !matches!(
+            self.transformation.quantity,
+            Quantity::AtLeastZero | Quantity::AtLeastOne
+        ) {
+            return Err(syn::Error::new_spanned(
+                &self.transformation.output_type,
+                "`join_iter!` requires the outermost quantity to be `Vec` or `Vec0`",
+            ));
+        }
+
+        Ok(Self {
+            streamed: true,
+            ..self
+        })
+    }
+
+    /// Returns the accumulated maps instead of building the outputs from them.
+    pub(crate) fn grouped(self) -> Self {
+        Self {
+            grouped: true,
+            ..self
+        }
+    }
+
     fn map_type(&self) -> TokenStream {
-        self.transformation.map_type()
+        let hasher = if let Some(hasher) = &self.hasher {
+            hasher.to_token_stream()
+        } else {
+            quote! { __benzina::__private::Hasher }
+        };
+        if self.sorted {
+            self.transformation.sorted_vec_type(&hasher)
+        } else {
+            self.transformation.map_type(&hasher)
+        }
     }
 
     fn accumulator(&self) -> TokenStream {
         let Self {
             input,
-            transformation: _,
+            crate_name: _,
+            hasher: _,
+            transformation,
+            sorted,
+            streamed: _,
+            grouped: _,
         } = self;
-        let accumulator = self.transformation.accumulator(None);
+        let accumulator = if *sorted {
+            transformation.sorted_accumulator()
+        } else {
+            transformation.accumulator(None)
+        };
         quote! {
             for row in #input {
                 #accumulator
@@ -55,7 +126,21 @@ impl Join {
 
     fn presenter(&self) -> TokenStream {
         let accumulator = quote! { accumulator };
-        self.transformation.presenter(&accumulator)
+        let values = if self.sorted {
+            quote! {
+                __benzina::__private::std::iter::Iterator::map(
+                    __benzina::__private::std::iter::IntoIterator::into_iter(#accumulator),
+                    |(_id, item)| item
+                )
+            }
+        } else {
+            quote! { __benzina::__private::IndexMap::into_values(#accumulator) }
+        };
+        if self.streamed {
+            self.transformation.items(&values)
+        } else {
+            self.transformation.presenter(&values)
+        }
     }
 }
 
@@ -63,10 +148,24 @@ impl ToTokens for Join {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let map_type = self.map_type();
         let accumulator = self.accumulator();
-        let presenter = self.presenter();
+        let presenter = if self.grouped {
+            quote! { accumulator }
+        } else {
+            self.presenter()
+        };
+        let new_accumulator = if self.sorted {
+            quote! { __benzina::__private::std::vec::Vec::new() }
+        } else {
+            quote! { __benzina::__private::new_indexmap() }
+        };
+        let crate_name = crate::crate_name(&self.crate_name);
         tokens.extend(quote! {
             {
-                let mut accumulator: #map_type = ::benzina::__private::new_indexmap();
+                // Every path below goes through this alias, so that a custom
+                // `crate` only needs to be honored here
+                use #crate_name as __benzina;
+
+                let mut accumulator: #map_type = #new_accumulator;
                 #accumulator
                 #presenter
             }
@@ -75,10 +174,10 @@ impl ToTokens for Join {
 }
 
 impl NestedOrNot {
-    fn map_type_values(&self) -> Vec<TokenStream> {
+    fn map_type_values(&self, hasher: &TokenStream) -> Vec<TokenStream> {
         match self {
-            Self::Nested(nested) => vec![nested.map_type()],
-            Self::Not(not) => not.map_type_values(),
+            Self::Nested(nested) => vec![nested.map_type(hasher)],
+            Self::Not(not) => not.map_type_values(hasher),
         }
     }
 
@@ -100,19 +199,47 @@ impl NestedOrNot {
 
     fn presenter(&self, accumulator: &TokenStream) -> TokenStream {
         match self {
-            Self::Nested(nested) => nested.presenter(accumulator),
+            Self::Nested(nested) => nested.presenter(&quote! {
+                __benzina::__private::IndexMap::into_values(#accumulator)
+            }),
             Self::Not(not) => not.presenter(accumulator),
         }
     }
 }
 
 impl Transformation {
-    fn map_type(&self) -> TokenStream {
+    /// Marks the fields reading a tuple index that is also read by other fields.
+    pub(super) fn mark_shared(&mut self) {
+        let mut reads = BTreeMap::<usize, usize>::new();
+        self.for_each_field(&mut |field| {
+            *reads.entry(field.tuple_index).or_default() += 1;
+        });
+        self.for_each_field(&mut |field| field.shared = reads[&field.tuple_index] > 1);
+    }
+
+    fn for_each_field(&mut self, f: &mut impl FnMut(&mut NoTransformation)) {
+        for (_name, entry) in &mut self.entries {
+            match entry {
+                NestedOrNot::Nested(nested) => nested.for_each_field(f),
+                NestedOrNot::Not(not) => f(not),
+            }
+        }
+    }
+
+    fn map_type(&self, hasher: &TokenStream) -> TokenStream {
+        let values = self
+            .entries
+            .iter()
+            .flat_map(|(_key, value)| value.map_type_values(hasher));
+        quote! { __benzina::__private::IndexMap::<_, (#(#values),*), #hasher> }
+    }
+
+    fn sorted_vec_type(&self, hasher: &TokenStream) -> TokenStream {
         let values = self
             .entries
             .iter()
-            .flat_map(|(_key, value)| value.map_type_values());
-        quote! { ::benzina::__private::IndexMap::<_, (#(#values),*)> }
+            .flat_map(|(_key, value)| value.map_type_values(hasher));
+        quote! { __benzina::__private::std::vec::Vec::<(_, (#(#values),*))> }
     }
 
     fn accumulator(&self, accumulator_index: Option<usize>) -> TokenStream {
@@ -122,21 +249,78 @@ impl Transformation {
         } else {
             quote! { accumulator }
         };
-        let one = self
-            .entries
-            .iter()
-            .find_map(|(_name, entry)| match entry {
-                NestedOrNot::Nested(_nested) => None,
-                NestedOrNot::Not(not) => Some(not),
+        let RowGroup {
+            wrapper,
+            id,
+            or_insert_tokens,
+            accumulator,
+        } = self.row_group();
+        quote! {
+            #wrapper {
+                let mut accumulator = __benzina::__private::indexmap::map::Entry::or_insert(
+                    __benzina::__private::IndexMap::entry(&mut #accumulator_index, #id),
+                    #or_insert_tokens
+                );
+                #(#accumulator)*
+            }
+        }
+    }
+
+    /// Like [`Self::accumulator`], but appends a new group only when the id
+    /// differs from the one of the previous row
+    fn sorted_accumulator(&self) -> TokenStream {
+        let RowGroup {
+            wrapper,
+            id,
+            or_insert_tokens,
+            accumulator,
+        } = self.row_group();
+        quote! {
+            #wrapper {
+                let id = #id;
+                let is_new_group = match <[_]>::last(&accumulator) {
+                    __benzina::__private::std::option::Option::Some((last_id, _)) => *last_id != id,
+                    __benzina::__private::std::option::Option::None => true,
+                };
+                if is_new_group {
+                    __benzina::__private::std::vec::Vec::push(&mut accumulator, (id, #or_insert_tokens));
+                }
+                let (_id, accumulator) = match <[_]>::last_mut(&mut accumulator) {
+                    __benzina::__private::std::option::Option::Some(group) => group,
+                    __benzina::__private::std::option::Option::None => __benzina::__private::std::unreachable!(),
+                };
+                #(#accumulator)*
+            }
+        }
+    }
+
+    /// The field whose id identifies the groups of rows: the struct base, if
+    /// any, or the first `One` or `AssumeOne` field
+    fn driver(&self) -> Option<&NoTransformation> {
+        self.base().or_else(|| {
+            self.entries.iter().find_map(|(_name, entry)| match entry {
+                NestedOrNot::Not(not)
+                    if matches!(not.quantity, Quantity::One | Quantity::AssumeOne) =>
+                {
+                    Some(not)
+                }
+                _ => None,
             })
-            .unwrap();
+        })
+    }
+
+    fn row_group(&self) -> RowGroup {
+        let one = self
+            .driver()
+            .expect("the driver has been checked while parsing");
         let one_tuple_index = Index::from(one.tuple_index);
 
         let mut tuple_index_overwrites = BTreeMap::new();
         let wrapper = if matches!(self.quantity, Quantity::AtLeastZero) {
             let name = Ident::new(&format!("unwrapped{}", one.tuple_index), Span::call_site());
             tuple_index_overwrites.insert(one.tuple_index, quote! { #name });
-            quote! { if let ::benzina::__private::std::option::Option::Some(#name) = row.#one_tuple_index }
+            let row = one.row_value();
+            quote! { if let __benzina::__private::std::option::Option::Some(#name) = #row }
         } else {
             quote! {}
         };
@@ -154,17 +338,24 @@ impl Transformation {
             quote! { row.#one_tuple_index }
         };
         let id = Identifiable { table: one_name };
-        quote! {
-            #wrapper {
-                let mut accumulator = ::benzina::__private::indexmap::map::Entry::or_insert(
-                    ::benzina::__private::IndexMap::entry(&mut #accumulator_index, #id),
-                    #or_insert_tokens
-                );
-                #(#accumulator)*
-            }
+        RowGroup {
+            wrapper,
+            id: id.into_token_stream(),
+            or_insert_tokens,
+            accumulator: accumulator.collect(),
         }
     }
 
+    /// The entry used as the base of the struct update syntax, if any
+    fn base(&self) -> Option<&NoTransformation> {
+        self.entries
+            .iter()
+            .find_map(|(name, entry)| match (name, entry) {
+                (Field::Base(_), NestedOrNot::Not(not)) => Some(not),
+                _ => None,
+            })
+    }
+
     fn or_insert(&self, tuple_index_overwrites: &BTreeMap<usize, TokenStream>) -> Vec<TokenStream> {
         self.entries
             .iter()
@@ -172,21 +363,28 @@ impl Transformation {
             .collect()
     }
 
-    fn presenter(&self, accumulator: &TokenStream) -> TokenStream {
+    /// Builds an iterator over the outputs from the accumulated `values`.
+    ///
+    /// The items are `Result`s if [`Self::is_nested_result`].
+    fn items(&self, values: &TokenStream) -> TokenStream {
         let Self {
-            quantity,
+            quantity: _,
             output_type,
             entries,
         } = self;
-        let is_result = self.is_result();
 
         let entries = entries.iter().enumerate().map(|(i, (name, entry))| {
             let item = Ident::new("item", Span::call_site());
             let ii = Index::from(i);
             let item = quote! { #item.#ii };
             let entry = entry.presenter(&item);
-            quote! {
-                #name: #entry
+            match name {
+                Field::Member(name) => quote! {
+                    #name: #entry
+                },
+                Field::Base(dot2) => quote! {
+                    #dot2 #entry
+                },
             }
         });
         let output = quote! {
@@ -196,9 +394,9 @@ impl Transformation {
         };
         let map_closure = if self.is_nested_result() {
             quote! {
-                |item| ::benzina::__private::std::result::Result::Ok::<
+                |item| __benzina::__private::std::result::Result::Ok::<
                     #output_type,
-                    ::benzina::__private::diesel::result::Error
+                    __benzina::__private::diesel::result::Error
                 >(#output)
             }
         } else {
@@ -206,22 +404,28 @@ impl Transformation {
                 |item| #output
             }
         };
-        let iterator = quote! {
-            ::benzina::__private::std::iter::Iterator::map(
-                ::benzina::__private::IndexMap::into_values(#accumulator),
+        quote! {
+            __benzina::__private::std::iter::Iterator::map(
+                #values,
                 #map_closure
             )
-        };
-        match quantity {
+        }
+    }
+
+    /// Builds the output from an iterator over the accumulated `values`.
+    fn presenter(&self, values: &TokenStream) -> TokenStream {
+        let is_result = self.is_result();
+        let iterator = self.items(values);
+        match self.quantity {
             Quantity::MaybeOne => {
                 let item = quote! {
-                    ::benzina::__private::std::iter::Iterator::next(
+                    __benzina::__private::std::iter::Iterator::next(
                         &mut #iterator
                     )
                 };
                 if is_result {
                     quote! {
-                        ::benzina::__private::std::option::Option::transpose(
+                        __benzina::__private::std::option::Option::transpose(
                             #item
                         )?
                     }
@@ -231,41 +435,42 @@ impl Transformation {
             }
             Quantity::One | Quantity::AssumeOne => {
                 quote! {
-                    match ::benzina::__private::std::iter::Iterator::next(
+                    match __benzina::__private::std::iter::Iterator::next(
                         &mut #iterator
                     ) {
-                        ::benzina::__private::std::option::Option::Some(item) => item,
-                        ::benzina::__private::std::option::Option::None => return ::benzina::__private::std::result::Result::Err(
-                            ::benzina::__private::diesel::result::Error::NotFound
+                        __benzina::__private::std::option::Option::Some(item) => item,
+                        __benzina::__private::std::option::Option::None => return __benzina::__private::std::result::Result::Err(
+                            __benzina::__private::diesel::result::Error::NotFound
                         )
                     }
                 }
             }
             Quantity::AtLeastZero | Quantity::AtLeastOne => {
-                if is_result {
-                    quote! {
-                        ::benzina::__private::std::iter::Iterator::collect::<
-                            ::benzina::__private::std::result::Result<
-                                ::benzina::__private::std::vec::Vec<_>,
-                                ::benzina::__private::diesel::result::Error,
-                            >
-                        >(
-                            #iterator
-                        )?
-                    }
+                let item = if is_result {
+                    quote! { item? }
                 } else {
-                    quote! {
-                        ::benzina::__private::std::iter::Iterator::collect::<
-                            ::benzina::__private::std::vec::Vec<_>
-                        >(
-                            #iterator
-                        )
+                    quote! { item }
+                };
+                quote! {
+                    {
+                        let iterator = #iterator;
+                        let mut output = __benzina::__private::std::vec::Vec::with_capacity(
+                            __benzina::__private::std::iter::ExactSizeIterator::len(&iterator)
+                        );
+                        for item in iterator {
+                            __benzina::__private::std::vec::Vec::push(&mut output, #item);
+                        }
+                        output
                     }
                 }
             }
         }
     }
 
+    /// Whether the presenter of this transformation can fail
+    ///
+    /// `AssumeOne` leaves aren't taken into account: their `NULL` check
+    /// happens while accumulating the rows, outside of the presenter.
     fn is_result(&self) -> bool {
         match self.quantity {
             Quantity::One | Quantity::AssumeOne => true,
@@ -281,31 +486,52 @@ impl Transformation {
     }
 }
 
+/// The pieces of code needed to accumulate a row into its group
+struct RowGroup {
+    wrapper: TokenStream,
+    id: TokenStream,
+    or_insert_tokens: TokenStream,
+    accumulator: Vec<TokenStream>,
+}
+
 impl NoTransformation {
-    fn map_type_values(&self) -> Vec<TokenStream> {
+    fn map_type_values(&self, hasher: &TokenStream) -> Vec<TokenStream> {
         match self.quantity {
             Quantity::MaybeOne => vec![quote! {
-                ::benzina::__private::std::option::Option<_>
+                __benzina::__private::std::option::Option<_>
             }],
             Quantity::One | Quantity::AssumeOne => vec![quote! {
                 _
             }],
             Quantity::AtLeastZero | Quantity::AtLeastOne => vec![quote! {
-                ::benzina::__private::IndexMap::<_, _>
+                __benzina::__private::IndexMap::<_, _, #hasher>
             }],
         }
     }
 
-    fn accumulator(&self, accumulator_index: usize) -> TokenStream {
+    /// Reads the value of this field out of the row, cloning it if it's shared
+    fn row_value(&self) -> TokenStream {
         let tuple_index = Index::from(self.tuple_index);
-        let row = quote! { row.#tuple_index };
+        self.value(&quote! { row.#tuple_index })
+    }
+
+    fn value(&self, value: &TokenStream) -> TokenStream {
+        if self.shared {
+            quote! { __benzina::__private::std::clone::Clone::clone(&#value) }
+        } else {
+            quote! { #value }
+        }
+    }
+
+    fn accumulator(&self, accumulator_index: usize) -> TokenStream {
+        let row = self.row_value();
 
         let accumulator_index = Index::from(accumulator_index);
         match self.quantity {
             Quantity::MaybeOne => quote! {
                 {
-                    if let ::benzina::__private::std::option::Option::Some(item) = #row {
-                        accumulator.#accumulator_index = ::benzina::__private::std::option::Option::Some(item);
+                    if let __benzina::__private::std::option::Option::Some(item) = #row {
+                        accumulator.#accumulator_index = __benzina::__private::std::option::Option::Some(item);
                     }
                 }
             },
@@ -316,9 +542,9 @@ impl NoTransformation {
                 };
                 quote! {
                     {
-                        if let ::benzina::__private::std::option::Option::Some(item) = #row {
-                            ::benzina::__private::indexmap::map::Entry::or_insert(
-                                ::benzina::__private::IndexMap::entry(&mut accumulator.#accumulator_index, #id),
+                        if let __benzina::__private::std::option::Option::Some(item) = #row {
+                            __benzina::__private::indexmap::map::Entry::or_insert(
+                                __benzina::__private::IndexMap::entry(&mut accumulator.#accumulator_index, #id),
                                 item
                             );
                         }
@@ -332,8 +558,8 @@ impl NoTransformation {
                 quote! {
                     {
                         let item = #row;
-                        ::benzina::__private::indexmap::map::Entry::or_insert(
-                            ::benzina::__private::IndexMap(&mut accumulator.#accumulator_index, #id),
+                        __benzina::__private::indexmap::map::Entry::or_insert(
+                            __benzina::__private::IndexMap::entry(&mut accumulator.#accumulator_index, #id),
                             item
                         );
                     }
@@ -344,27 +570,26 @@ impl NoTransformation {
 
     fn or_insert(&self, tuple_index_overwrites: &BTreeMap<usize, TokenStream>) -> Vec<TokenStream> {
         match self.quantity {
-            Quantity::MaybeOne => vec![quote! { ::benzina::__private::std::option::Option::None }],
+            Quantity::MaybeOne => vec![quote! { __benzina::__private::std::option::Option::None }],
             Quantity::One => {
                 if let Some(overwrite) = tuple_index_overwrites.get(&self.tuple_index) {
-                    vec![quote! { #overwrite }]
+                    vec![self.value(overwrite)]
                 } else {
-                    let tuple_index = Index::from(self.tuple_index);
-                    vec![quote! { row.#tuple_index }]
+                    vec![self.row_value()]
                 }
             }
             Quantity::AssumeOne => {
                 if let Some(overwrite) = tuple_index_overwrites.get(&self.tuple_index) {
-                    vec![quote! { #overwrite }]
+                    vec![self.value(overwrite)]
                 } else {
-                    let tuple_index = Index::from(self.tuple_index);
+                    let row = self.row_value();
                     vec![quote! {
-                        if let ::benzina::__private::std::option::Option::Some(item) = row.#tuple_index {
+                        if let __benzina::__private::std::option::Option::Some(item) = #row {
                             item
                         } else {
-                            return ::benzina::__private::std::result::Result::Err(::benzina::__private::diesel::result::Error::DeserializationError(
-                                ::benzina::__private::std::boxed::Box::from(
-                                    ::benzina::__private::std::borrow::ToOwned::to_owned(
+                            return __benzina::__private::std::result::Result::Err(__benzina::__private::diesel::result::Error::DeserializationError(
+                                __benzina::__private::std::boxed::Box::from(
+                                    __benzina::__private::std::borrow::ToOwned::to_owned(
                                         "`AssumeOne` value is null"
                                     )
                                 )
@@ -386,8 +611,8 @@ impl NoTransformation {
             }
             Quantity::AtLeastZero | Quantity::AtLeastOne => {
                 quote! {
-                    ::benzina::__private::std::iter::Iterator::collect::<::benzina::__private::std::vec::Vec<_>>(
-                        ::benzina::__private::IndexMap::into_values(#accumulator)
+                    __benzina::__private::std::iter::Iterator::collect::<__benzina::__private::std::vec::Vec<_>>(
+                        __benzina::__private::IndexMap::into_values(#accumulator)
                     )
                 }
             }
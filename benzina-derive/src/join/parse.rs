@@ -1,21 +1,51 @@
+use std::collections::HashSet;
+
 use syn::{
-    Ident, LitInt, Token, braced,
+    LitInt, Member, Path, PathArguments, Token, Type, braced,
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
 };
 
-use super::{Join, NestedOrNot, NoTransformation, Transformation};
+use super::{Field, Join, NestedOrNot, NoTransformation, Transformation, quantity::Quantity};
+
+mod kw {
+    syn::custom_keyword!(hasher);
+}
 
 impl Parse for Join {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let input_ = input.parse()?;
         input.parse::<Token![,]>()?;
-        let transformation = input.parse()?;
+        let crate_name = if input.peek(Token![crate]) && input.peek2(Token![=]) {
+            input.parse::<Token![crate]>()?;
+            input.parse::<Token![=]>()?;
+            let crate_name = input.call(Path::parse_mod_style)?;
+            input.parse::<Token![,]>()?;
+            Some(crate_name)
+        } else {
+            None
+        };
+        let hasher = if input.peek(kw::hasher) && input.peek2(Token![=]) {
+            input.parse::<kw::hasher>()?;
+            input.parse::<Token![=]>()?;
+            let hasher = input.parse::<Type>()?;
+            input.parse::<Token![,]>()?;
+            Some(hasher)
+        } else {
+            None
+        };
+        let mut transformation = input.parse::<Transformation>()?;
         input.parse::<Token![,]>()?;
+        transformation.mark_shared();
 
         Ok(Self {
             input: input_,
+            crate_name,
+            hasher,
             transformation,
+            sorted: false,
+            streamed: false,
+            grouped: false,
         })
     }
 }
@@ -37,24 +67,79 @@ impl Parse for Transformation {
         let quantity = input.parse()?;
         input.parse::<Token![<]>()?;
 
-        let output_type = input.parse()?;
+        let mut output_type = input.parse::<Path>()?;
+        for segment in &mut output_type.segments {
+            if let PathArguments::AngleBracketed(arguments) = &mut segment.arguments {
+                arguments.colon2_token.get_or_insert_with(Default::default);
+            }
+        }
         let content;
         braced!(content in input);
 
         let entries = Punctuated::parse_terminated_with(&content, |input| {
-            let field = input.parse::<Ident>()?;
+            if input.peek(Token![..]) {
+                let dot2 = input.parse::<Token![..]>()?;
+                let value = input.parse::<NoTransformation>()?;
+                if !matches!(value.quantity, Quantity::One | Quantity::AssumeOne) {
+                    return Err(syn::Error::new_spanned(
+                        dot2,
+                        "the struct base must be `One` or `AssumeOne`",
+                    ));
+                }
+                return Ok((Field::Base(dot2), NestedOrNot::Not(value)));
+            }
+
+            let field = input.parse::<Member>()?;
             input.parse::<Token![:]>()?;
             let value = input.parse::<NestedOrNot>()?;
-            Ok((field, value))
+            Ok((Field::Member(field), value))
         })?;
+        let len = entries.len();
+        if let Some((Field::Base(dot2), _)) = entries
+            .iter()
+            .take(len.saturating_sub(1))
+            .find(|(field, _)| matches!(field, Field::Base(_)))
+        {
+            return Err(syn::Error::new_spanned(
+                dot2,
+                "the struct base must be the last entry",
+            ));
+        }
 
         input.parse::<Token![>]>()?;
 
-        Ok(Self {
+        let mut names = HashSet::new();
+        for (field, _value) in &entries {
+            if let Field::Member(member) = field {
+                if !names.insert(member) {
+                    return Err(syn::Error::new_spanned(
+                        member,
+                        "the field is listed more than once",
+                    ));
+                }
+            }
+        }
+
+        let transformation = Self {
             quantity,
             output_type,
             entries,
-        })
+        };
+        if transformation.driver().is_none() {
+            return Err(syn::Error::new_spanned(
+                &transformation.output_type,
+                format!(
+                    "`{}` needs a `One` or `AssumeOne` field to group the rows by",
+                    transformation
+                        .output_type
+                        .segments
+                        .last()
+                        .map_or_else(String::new, |segment| segment.ident.to_string())
+                ),
+            ));
+        }
+
+        Ok(transformation)
     }
 }
 
@@ -67,6 +152,7 @@ impl Parse for NoTransformation {
         Ok(Self {
             quantity,
             tuple_index,
+            shared: false,
         })
     }
 }
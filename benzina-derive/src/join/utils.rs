@@ -6,11 +6,15 @@ pub(super) struct NewIndexMap;
 impl ToTokens for NewIndexMap {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         tokens.extend(quote! {
-            ::benzina::__private::new_indexmap::<_, _>()
+            __benzina::__private::new_indexmap::<_, _, _>()
         });
     }
 }
 
+/// Clones the id of the entity `table`, which diesel returns by reference
+///
+/// `Copy` ids, like integers and `Uuid`s, go through the same path: their
+/// `Clone` is a plain copy, so a separate one would only duplicate the code.
 pub(super) struct Identifiable<T> {
     pub(super) table: T,
 }
@@ -19,8 +23,8 @@ impl<T: ToTokens> ToTokens for Identifiable<T> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let Self { table } = self;
         tokens.extend(quote! {
-            ::benzina::__private::deep_clone::DeepClone::deep_clone(
-                &(<_ as ::benzina::__private::diesel::associations::Identifiable>::id(&#table),)
+            __benzina::__private::deep_clone::DeepClone::deep_clone(
+                &(<_ as __benzina::__private::diesel::associations::Identifiable>::id(&#table),)
             )
         });
     }
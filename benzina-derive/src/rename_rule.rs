@@ -1,8 +1,9 @@
+use std::borrow::Cow;
 use std::str::FromStr;
 
 use heck::{
     ToKebabCase as _, ToLowerCamelCase as _, ToPascalCase as _, ToShoutyKebabCase,
-    ToShoutySnakeCase as _, ToSnakeCase as _,
+    ToShoutySnakeCase as _, ToSnakeCase as _, ToTitleCase as _, ToTrainCase as _,
 };
 
 #[derive(Copy, Clone)]
@@ -16,12 +17,67 @@ pub(crate) enum RenameRule {
     ScreamingSnakeCase,
     KebabCase,
     ScreamingKebabCase,
+    TitleCase,
+    TrainCase,
+}
+
+/// How consecutive uppercase letters are split into words by a [`RenameRule`]
+#[derive(Copy, Clone)]
+pub(crate) enum Acronyms {
+    /// Consecutive uppercase letters form a single word: `HTTPError` is `http_error`
+    Group,
+    /// Every uppercase letter starts a new word: `HTTPError` is `h_t_t_p_error`
+    Split,
+}
+
+impl Acronyms {
+    fn apply(self, val: &str) -> Cow<'_, str> {
+        match self {
+            Self::Group => Cow::Borrowed(val),
+            Self::Split => {
+                let mut out = String::with_capacity(val.len() * 2);
+                let mut prev_is_uppercase = false;
+                for c in val.chars() {
+                    if c.is_uppercase() && prev_is_uppercase {
+                        out.push('_');
+                    }
+                    prev_is_uppercase = c.is_uppercase();
+                    out.push(c);
+                }
+                Cow::Owned(out)
+            }
+        }
+    }
+}
+
+impl FromStr for Acronyms {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "group" => Ok(Self::Group),
+            "split" => Ok(Self::Split),
+            _ => Err("invalid acronyms rule, expected `group` or `split`".to_owned()),
+        }
+    }
 }
 
 impl RenameRule {
-    pub(crate) fn format(self, val: &str) -> String {
+    pub(crate) fn format(self, val: &str, acronyms: Acronyms) -> String {
+        let val = match self {
+            Self::None | Self::LowerCase | Self::UpperCase => Cow::Borrowed(val),
+            Self::PascalCase
+            | Self::CamelCase
+            | Self::SnakeCase
+            | Self::ScreamingSnakeCase
+            | Self::KebabCase
+            | Self::ScreamingKebabCase
+            | Self::TitleCase
+            | Self::TrainCase => acronyms.apply(val),
+        };
+
         match self {
-            Self::None => val.to_owned(),
+            Self::None => val.into_owned(),
             Self::LowerCase => val.to_lowercase(),
             Self::UpperCase => val.to_uppercase(),
             Self::PascalCase => val.to_pascal_case(),
@@ -30,6 +86,8 @@ impl RenameRule {
             Self::ScreamingSnakeCase => val.to_shouty_snake_case(),
             Self::KebabCase => val.to_kebab_case(),
             Self::ScreamingKebabCase => val.to_shouty_kebab_case(),
+            Self::TitleCase => val.to_title_case(),
+            Self::TrainCase => val.to_train_case(),
         }
     }
 }
@@ -47,7 +105,84 @@ impl FromStr for RenameRule {
             "SCREAMING_SNAKE_CASE" => Ok(Self::ScreamingSnakeCase),
             "kebab-case" => Ok(Self::KebabCase),
             "SCREAMING-KEBAB-CASE" => Ok(Self::ScreamingKebabCase),
+            "Title Case" => Ok(Self::TitleCase),
+            "Train-Case" => Ok(Self::TrainCase),
             _ => Err("invalid rename rule".to_owned()),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Acronyms, RenameRule};
+
+    #[test]
+    fn acronyms() {
+        let cases = [
+            (RenameRule::SnakeCase, "http_error", "h_t_t_p_error"),
+            (
+                RenameRule::ScreamingKebabCase,
+                "HTTP-ERROR",
+                "H-T-T-P-ERROR",
+            ),
+            (RenameRule::CamelCase, "httpError", "hTTPError"),
+            (RenameRule::LowerCase, "httperror", "httperror"),
+            (RenameRule::None, "HTTPError", "HTTPError"),
+        ];
+        for (rule, grouped, split) in cases {
+            assert_eq!(rule.format("HTTPError", Acronyms::Group), grouped);
+            assert_eq!(rule.format("HTTPError", Acronyms::Split), split);
+        }
+
+        assert_eq!(
+            RenameRule::SnakeCase.format("ServerHTTP", Acronyms::Group),
+            "server_http"
+        );
+        assert_eq!(
+            RenameRule::SnakeCase.format("ServerHTTP", Acronyms::Split),
+            "server_h_t_t_p"
+        );
+    }
+
+    #[test]
+    fn title_and_train_case() {
+        let cases = [
+            ("GuineaPig", "Guinea Pig", "Guinea-Pig"),
+            (
+                "VeryLongVariantName",
+                "Very Long Variant Name",
+                "Very-Long-Variant-Name",
+            ),
+            ("Cat", "Cat", "Cat"),
+        ];
+        for (variant, title, train) in cases {
+            assert_eq!(
+                RenameRule::TitleCase.format(variant, Acronyms::Group),
+                title
+            );
+            assert_eq!(
+                RenameRule::TrainCase.format(variant, Acronyms::Group),
+                train
+            );
+        }
+
+        assert_eq!(
+            RenameRule::TitleCase.format("HTTPError", Acronyms::Group),
+            "Http Error"
+        );
+        assert_eq!(
+            RenameRule::TrainCase.format("HTTPError", Acronyms::Split),
+            "H-T-T-P-Error"
+        );
+
+        assert!(matches!(
+            "Title Case".parse::<RenameRule>(),
+            Ok(RenameRule::TitleCase)
+        ));
+        assert!(matches!(
+            "Train-Case".parse::<RenameRule>(),
+            Ok(RenameRule::TrainCase)
+        ));
+        assert!("title case".parse::<RenameRule>().is_err());
+    }
+}
@@ -0,0 +1,7 @@
+#![cfg(feature = "postgres")]
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
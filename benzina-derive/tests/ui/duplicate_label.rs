@@ -0,0 +1,8 @@
+#[derive(benzina_derive::Enum)]
+#[benzina(sql_type = diesel::sql_types::Text, rename_all = "snake_case")]
+enum Error {
+    HttpError,
+    HTTPError,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+#[derive(benzina_derive::Enum)]
+#[benzina(sql_type = diesel::sql_types::Text)]
+enum Animal {
+    Goose,
+    #[benzina(rename = "Goose")]
+    Oca,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+struct UserWithPosts {
+    id: i32,
+    posts: Vec<i32>,
+}
+
+fn main() {
+    let records: Vec<(UserWithPosts, Option<i32>)> = Vec::new();
+    let _ = benzina_derive::join! {
+        records,
+        Vec<UserWithPosts {
+            ..One<0>,
+            posts: Vec0<1>,
+        }>,
+    };
+}
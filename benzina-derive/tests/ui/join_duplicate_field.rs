@@ -0,0 +1,16 @@
+struct UserWithPosts {
+    user: i32,
+    posts: Vec<i32>,
+}
+
+fn main() {
+    let records: Vec<(i32, Option<i32>)> = Vec::new();
+    let _ = benzina_derive::join! {
+        records,
+        Vec<UserWithPosts {
+            user: One<0>,
+            posts: Vec0<1>,
+            user: One<0>,
+        }>,
+    };
+}
@@ -0,0 +1,15 @@
+struct UserWithPosts {
+    user: Option<i32>,
+    posts: Vec<i32>,
+}
+
+fn main() {
+    let records: Vec<(Option<i32>, Option<i32>)> = Vec::new();
+    let _ = benzina_derive::join! {
+        records,
+        Vec<UserWithPosts {
+            user: Option<0>,
+            posts: Vec0<1>,
+        }>,
+    };
+}
@@ -0,0 +1,10 @@
+#[derive(benzina_derive::Enum)]
+#[benzina(
+    sql_type = diesel::sql_types::Text,
+    rename_all(pg = "snake_case")
+)]
+enum Animal {
+    GuineaPig,
+}
+
+fn main() {}
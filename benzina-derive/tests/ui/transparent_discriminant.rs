@@ -0,0 +1,10 @@
+const HIGH: isize = 10;
+
+#[derive(benzina_derive::Enum)]
+#[benzina(sql_type = diesel::sql_types::Integer, transparent)]
+enum Priority {
+    Low,
+    High = HIGH,
+}
+
+fn main() {}
@@ -0,0 +1,148 @@
+use std::{hint::black_box, io::Write as _, num::NonZeroU32};
+
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use diesel::{
+    deserialize::FromSql,
+    pg::{Pg, PgMetadataLookup, PgTypeMetadata, PgValue},
+    query_builder::{BindCollector, bind_collector::RawBytesBindCollector},
+    serialize::{IsNull, Output, ToSql},
+};
+
+#[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+#[diesel(postgres_type(name = "animal"))]
+struct AnimalType;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, benzina::Enum)]
+#[benzina(sql_type = AnimalType, rename_all = "snake_case", labels)]
+enum Animal {
+    Rabbit,
+    Duck,
+    Goose,
+    Chicken,
+    GuineaPig,
+    Horse,
+    Donkey,
+    Llama,
+}
+
+/// `Animal` with a `ToSql` indexing a table of labels by discriminant, to
+/// compare against the `match` generated by the derive
+#[derive(Debug, Copy, Clone)]
+enum IndexedAnimal {
+    Rabbit,
+    Duck,
+    Goose,
+    Chicken,
+    GuineaPig,
+    Horse,
+    Donkey,
+    Llama,
+}
+
+impl IndexedAnimal {
+    const VARIANTS: [Self; 8] = [
+        Self::Rabbit,
+        Self::Duck,
+        Self::Goose,
+        Self::Chicken,
+        Self::GuineaPig,
+        Self::Horse,
+        Self::Donkey,
+        Self::Llama,
+    ];
+
+    const LABELS: &[&[u8]] = &[
+        b"rabbit",
+        b"duck",
+        b"goose",
+        b"chicken",
+        b"guinea_pig",
+        b"horse",
+        b"donkey",
+        b"llama",
+    ];
+}
+
+impl ToSql<AnimalType, Pg> for IndexedAnimal {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> diesel::serialize::Result {
+        out.write_all(Self::LABELS[*self as usize])?;
+        Ok(IsNull::No)
+    }
+}
+
+const ROWS: usize = 1_000_000;
+
+const OID: u32 = 16_385;
+
+struct FixedLookup;
+
+impl PgMetadataLookup for FixedLookup {
+    fn lookup_type(&mut self, _type_name: &str, _schema: Option<&str>) -> PgTypeMetadata {
+        PgTypeMetadata::new(OID, OID + 1)
+    }
+}
+
+/// Collects the binds of `rows` into `collector`, like a 1M-row insert does
+fn collect_binds<T>(collector: &mut RawBytesBindCollector<Pg>, rows: &[T])
+where
+    T: ToSql<AnimalType, Pg>,
+{
+    for row in rows {
+        collector
+            .push_bound_value::<AnimalType, _>(row, &mut FixedLookup)
+            .unwrap();
+    }
+}
+
+fn to_sql(c: &mut Criterion) {
+    let rows = Animal::VARIANTS
+        .iter()
+        .cycle()
+        .take(ROWS)
+        .map(|label| label.parse::<Animal>().unwrap())
+        .collect::<Vec<_>>();
+    let indexed_rows = IndexedAnimal::VARIANTS
+        .into_iter()
+        .cycle()
+        .take(ROWS)
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("to_sql 1M rows");
+    group.sample_size(20);
+    group.bench_function("derived match", |b| {
+        b.iter_batched_ref(
+            RawBytesBindCollector::new,
+            |collector| collect_binds(collector, &rows),
+            BatchSize::PerIteration,
+        );
+    });
+    group.bench_function("indexed table", |b| {
+        b.iter_batched_ref(
+            RawBytesBindCollector::new,
+            |collector| collect_binds(collector, &indexed_rows),
+            BatchSize::PerIteration,
+        );
+    });
+    group.finish();
+}
+
+fn from_sql(c: &mut Criterion) {
+    let labels = Animal::VARIANTS
+        .iter()
+        .cycle()
+        .take(ROWS)
+        .collect::<Vec<_>>();
+    let oid = NonZeroU32::new(OID).unwrap();
+
+    c.bench_function("from_sql 1M rows", |b| {
+        b.iter(|| {
+            for label in &labels {
+                let value = PgValue::new(label.as_bytes(), &oid);
+                black_box(<Animal as FromSql<AnimalType, Pg>>::from_sql(value).unwrap());
+            }
+        });
+    });
+}
+
+criterion_group!(benches, to_sql, from_sql);
+criterion_main!(benches);
@@ -0,0 +1,106 @@
+use std::hint::black_box;
+
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use diesel::Identifiable;
+
+diesel::table! {
+    users {
+        id -> Integer,
+        name -> Text,
+    }
+}
+
+diesel::table! {
+    posts {
+        id -> Integer,
+        user_id -> Integer,
+        message -> Text,
+    }
+}
+
+#[expect(dead_code, reason = "only building the structures is measured")]
+#[derive(Debug, Clone, Identifiable)]
+#[diesel(table_name = users)]
+struct User {
+    id: i32,
+    name: String,
+}
+
+#[expect(dead_code, reason = "only building the structures is measured")]
+#[derive(Debug, Clone, Identifiable)]
+#[diesel(table_name = posts)]
+struct Post {
+    id: i32,
+    user_id: i32,
+    message: String,
+}
+
+#[expect(dead_code, reason = "only building the structures is measured")]
+#[derive(Debug)]
+struct UserWithPosts {
+    user: User,
+    posts: Vec<Post>,
+}
+
+const USERS: i32 = 10_000;
+const POSTS_PER_USER: i32 = 10;
+
+/// The rows of `users LEFT JOIN posts ORDER BY users.id`
+fn sorted_records() -> Vec<(User, Option<Post>)> {
+    (0..USERS)
+        .flat_map(|user_id| {
+            let user = User {
+                id: user_id,
+                name: format!("user {user_id}"),
+            };
+            (0..POSTS_PER_USER).map(move |n| {
+                let post = Post {
+                    id: user_id * POSTS_PER_USER + n,
+                    user_id,
+                    message: format!("post {n}"),
+                };
+                (user.clone(), Some(post))
+            })
+        })
+        .collect()
+}
+
+fn join(c: &mut Criterion) {
+    let records = sorted_records();
+
+    let mut group = c.benchmark_group("join 100k sorted rows");
+    group.bench_function("join!", |b| {
+        b.iter_batched(
+            || records.clone(),
+            |records| {
+                black_box(benzina::join! {
+                    records,
+                    Vec<UserWithPosts {
+                        user: One<0>,
+                        posts: Vec0<1>,
+                    }>,
+                })
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    group.bench_function("join_sorted!", |b| {
+        b.iter_batched(
+            || records.clone(),
+            |records| {
+                black_box(benzina::join_sorted! {
+                    records,
+                    Vec<UserWithPosts {
+                        user: One<0>,
+                        posts: Vec0<1>,
+                    }>,
+                })
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    group.finish();
+}
+
+criterion_group!(benches, join);
+criterion_main!(benches);
@@ -1,3 +1,5 @@
+#[cfg(feature = "clap")]
+pub use ::clap;
 #[cfg(any(feature = "derive", feature = "typed-uuid"))]
 pub use ::diesel;
 #[cfg(feature = "derive")]
@@ -9,18 +11,45 @@ pub use ::std;
 pub use ::uuid;
 
 #[cfg(all(feature = "derive", feature = "rustc-hash"))]
-type Hasher = rustc_hash::FxBuildHasher;
+pub type Hasher = rustc_hash::FxBuildHasher;
 
 #[cfg(all(feature = "derive", not(feature = "rustc-hash")))]
-type Hasher = std::hash::RandomState;
+pub type Hasher = std::hash::RandomState;
 
 #[cfg(feature = "derive")]
-pub type IndexMap<K, V> = indexmap::IndexMap<K, V, Hasher>;
+pub type IndexMap<K, V, S = Hasher> = indexmap::IndexMap<K, V, S>;
 
 #[cfg(feature = "derive")]
 #[must_use]
-pub fn new_indexmap<K, V>() -> IndexMap<K, V> {
-    IndexMap::with_hasher(Hasher::default())
+pub fn new_indexmap<K, V, S: Default>() -> IndexMap<K, V, S> {
+    IndexMap::with_hasher(S::default())
+}
+
+#[cfg(feature = "derive")]
+pub fn group_children<I, K, C, FK, FC>(rows: I, mut key: FK, mut child: FC) -> Vec<(K, Vec<C>)>
+where
+    I: IntoIterator,
+    K: Eq + std::hash::Hash,
+    FK: FnMut(&I::Item) -> K,
+    FC: FnMut(I::Item) -> C,
+{
+    let mut groups = new_indexmap::<K, Vec<C>, Hasher>();
+    for row in rows {
+        groups.entry(key(&row)).or_default().push(child(row));
+    }
+    groups.into_iter().collect()
+}
+
+#[cfg(feature = "derive")]
+#[must_use]
+pub fn unknown_variant(
+    value: &str,
+    expected: &'static [&'static str],
+) -> crate::error::UnknownVariant {
+    crate::error::UnknownVariant {
+        value: value.to_owned(),
+        expected,
+    }
 }
 
 #[cfg(all(feature = "postgres", feature = "json"))]
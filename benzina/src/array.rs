@@ -1,4 +1,9 @@
-use std::fmt::Debug;
+use std::{
+    array,
+    fmt::{self, Debug},
+    num::NonZeroUsize,
+    slice, vec,
+};
 
 use diesel::{
     deserialize::{FromSql, FromSqlRow},
@@ -7,7 +12,10 @@ use diesel::{
     query_builder::{AstPass, QueryFragment, QueryId},
     result::QueryResult,
     serialize::ToSql,
-    sql_types::{self, BigInt, Bool, Double, Float, Integer, Nullable, SmallInt, Text},
+    sql_types::{
+        self, BigInt, Binary, Bool, Double, Float, HasSqlType, Integer, Nullable, SingleValue,
+        SmallInt, SqlType, Text,
+    },
 };
 
 use crate::{U15, U31, U63, error::InvalidArray};
@@ -27,6 +35,15 @@ use crate::{U15, U31, U63, error::InvalidArray};
 /// array_position(array_field, NULL) IS NULL
 /// ```
 ///
+/// The element type of the array is also checked at runtime, so that reading
+/// e.g. a `real[]` column as `Array<i32, N>` fails with an error naming the
+/// expected type instead of reinterpreting the items. Arrays of user-defined
+/// types, like domains over the expected type, are not checked.
+///
+/// `Array<T, 0>` is the empty array `'{}'`: reading any other array fails with
+/// [`InvalidArray::UnexpectedLength`]. Since PostgreSQL reports no dimensions
+/// for empty arrays, the `CHECK` constraint becomes `cardinality(array_field) = 0`.
+///
 /// This type is not intended to be used directly in the model but rather to be
 /// used with diesel [`serialize_as`] and [`deserialize_as`].
 ///
@@ -56,19 +73,8 @@ use crate::{U15, U31, U63, error::InvalidArray};
 /// #[derive(Debug)]
 /// struct UserFlags([bool; 5]);
 ///
-/// // needed by deserialize_as
-/// impl From<benzina::Array<bool, 5>> for UserFlags {
-///     fn from(value: benzina::Array<bool, 5>) -> Self {
-///         Self(value.into_inner())
-///     }
-/// }
-///
-/// // needed by serialize_as
-/// impl From<UserFlags> for benzina::Array<bool, 5> {
-///     fn from(value: UserFlags) -> Self {
-///         Self::new(value.0)
-///     }
-/// }
+/// // needed by deserialize_as and serialize_as
+/// benzina::array_deserialize_as!(UserFlags => bool, 5);
 ///
 /// diesel::table! {
 ///     users (id) {
@@ -83,7 +89,7 @@ use crate::{U15, U31, U63, error::InvalidArray};
 /// [`Array`]: diesel::sql_types::Array
 /// [`serialize_as`]: diesel::prelude::Insertable#optional-field-attributes
 /// [`deserialize_as`]: diesel::prelude::Queryable#deserialize_as-attribute
-#[derive(Debug, FromSqlRow)]
+#[derive(FromSqlRow, Clone, PartialEq, Eq, Hash)]
 pub struct Array<T, const N: usize>([T; N]);
 impl<T, const N: usize> Array<T, N> {
     #[must_use]
@@ -91,10 +97,89 @@ impl<T, const N: usize> Array<T, N> {
         Self(values)
     }
 
+    /// Creates a new array from the items of an iterator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidArray::UnexpectedLength`] if the iterator doesn't
+    /// yield exactly `N` items.
+    pub fn try_from_iter<I: IntoIterator<Item = T>>(iter: I) -> Result<Self, InvalidArray> {
+        collect_array(iter).map(Self)
+    }
+
     #[must_use]
     pub fn into_inner(self) -> [T; N] {
         self.0
     }
+
+    /// Returns the elements of the array as a slice.
+    #[must_use]
+    pub const fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    /// Returns an iterator over the elements of the array.
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.0.iter()
+    }
+
+    /// Returns the number of elements in the array, which is always `N`.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if the array has a length of 0.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for Array<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl<T: Default, const N: usize> Default for Array<T, N> {
+    fn default() -> Self {
+        Self(array::from_fn(|_| T::default()))
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Array<T, N> {
+    fn from(values: [T; N]) -> Self {
+        Self(values)
+    }
+}
+
+impl<T, const N: usize> TryFrom<Vec<T>> for Array<T, N> {
+    type Error = InvalidArray;
+
+    fn try_from(values: Vec<T>) -> Result<Self, Self::Error> {
+        <[T; N]>::try_from(values)
+            .map(Self)
+            .map_err(|_| InvalidArray::UnexpectedLength)
+    }
+}
+
+impl<T, const N: usize> IntoIterator for Array<T, N> {
+    type Item = T;
+    type IntoIter = array::IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a Array<T, N> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
 }
 
 /// A diesel [`Array`](diesel::sql_types::Array) serialization and deserialization wrapper __with__ NULL items
@@ -105,7 +190,7 @@ impl<T, const N: usize> Array<T, N> {
 ///   ```sql
 ///   array_position(array_field, NULL) IS NULL
 ///   ```
-#[derive(Debug, FromSqlRow)]
+#[derive(FromSqlRow, Clone, PartialEq, Eq, Hash)]
 pub struct ArrayWithNullableItems<T, const N: usize>([Option<T>; N]);
 impl<T, const N: usize> ArrayWithNullableItems<T, N> {
     #[must_use]
@@ -113,157 +198,1001 @@ impl<T, const N: usize> ArrayWithNullableItems<T, N> {
         Self(values)
     }
 
+    /// Creates a new array from the items of an iterator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidArray::UnexpectedLength`] if the iterator doesn't
+    /// yield exactly `N` items.
+    pub fn try_from_iter<I: IntoIterator<Item = Option<T>>>(iter: I) -> Result<Self, InvalidArray> {
+        collect_array(iter).map(Self)
+    }
+
     #[must_use]
     pub fn into_inner(self) -> [Option<T>; N] {
         self.0
     }
+
+    /// Returns the elements of the array as a slice.
+    #[must_use]
+    pub const fn as_slice(&self) -> &[Option<T>] {
+        &self.0
+    }
+
+    /// Returns an iterator over the elements of the array.
+    pub fn iter(&self) -> slice::Iter<'_, Option<T>> {
+        self.0.iter()
+    }
+
+    /// Applies `f` to every item that is not NULL, keeping the NULL ones.
+    pub fn map<U, F>(self, mut f: F) -> ArrayWithNullableItems<U, N>
+    where
+        F: FnMut(T) -> U,
+    {
+        ArrayWithNullableItems(self.0.map(|item| item.map(&mut f)))
+    }
+
+    /// Returns the items as an [`Array`], or `None` if any of them is NULL.
+    #[must_use]
+    pub fn transpose(self) -> Option<Array<T, N>> {
+        // Skipping the NULL items leaves fewer than `N` to collect
+        collect_array(self.0.into_iter().flatten()).ok().map(Array)
+    }
+
+    /// Returns the number of items that are not NULL.
+    #[must_use]
+    pub fn count_present(&self) -> usize {
+        self.iter().flatten().count()
+    }
+
+    /// Returns the number of elements in the array, which is always `N`.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if the array has a length of 0.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
 }
 
-macro_rules! impl_array {
-    (
-        $(
-            $rust_type:ident => $diesel_type:ident
-        ),*
-    ) => {
-        $(
-            impl<const N: usize> Expression for Array<$rust_type, N> {
-                type SqlType = sql_types::Array<Nullable<$diesel_type>>;
-            }
+impl<T: Debug, const N: usize> Debug for ArrayWithNullableItems<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+/// Creates an array full of `NULL` items.
+impl<T, const N: usize> Default for ArrayWithNullableItems<T, N> {
+    fn default() -> Self {
+        Self(array::from_fn(|_| None))
+    }
+}
 
-            impl< const N: usize> QueryId for Array<$rust_type, N> {
-                type QueryId = <sql_types::Array<Nullable<$diesel_type>> as QueryId>::QueryId;
+impl<T, const N: usize> IntoIterator for ArrayWithNullableItems<T, N> {
+    type Item = Option<T>;
+    type IntoIter = array::IntoIter<Option<T>, N>;
 
-                const HAS_STATIC_QUERY_ID: bool = <sql_types::Array<Nullable<$diesel_type>> as QueryId>::HAS_STATIC_QUERY_ID;
-            }
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
 
-            impl<const N: usize> QueryFragment<Pg> for Array<$rust_type, N>
-            {
-                fn walk_ast<'b>(&'b self, mut pass: AstPass<'_, 'b, Pg>) -> QueryResult<()> {
-                    pass.push_bind_param(self)?;
-                    Ok(())
-                }
-            }
+impl<'a, T, const N: usize> IntoIterator for &'a ArrayWithNullableItems<T, N> {
+    type Item = &'a Option<T>;
+    type IntoIter = slice::Iter<'a, Option<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// Collects exactly `N` items, consuming at most `N + 1` items from `iter`
+fn collect_array<T, const N: usize>(
+    iter: impl IntoIterator<Item = T>,
+) -> Result<[T; N], InvalidArray> {
+    iter.into_iter()
+        .take(N.saturating_add(1))
+        .collect::<Vec<_>>()
+        .try_into()
+        .map_err(|_| InvalidArray::UnexpectedLength)
+}
+
+/// A diesel [`Array`](diesel::sql_types::Array) serialization and deserialization wrapper for non-empty arrays
+///
+/// This type works like benzina [`Array`](crate::Array), except that the
+/// length is not known in advance: reading an empty array fails instead, so
+/// that [`first`](Self::first) and [`last`](Self::last) never have to deal with
+/// a missing item. To be completely safe, you should also add the following
+/// `CHECK` constraints:
+/// ```sql
+/// array_ndims(array_field) = 1 AND
+/// array_length(array_field, 1) > 0 AND
+/// array_position(array_field, NULL) IS NULL
+/// ```
+///
+/// ```
+/// use benzina::NonEmptyVec;
+///
+/// let tags = NonEmptyVec::new(String::from("rust"), vec![String::from("sql")]);
+/// assert_eq!(tags.first(), "rust");
+/// assert_eq!(tags.last(), "sql");
+/// assert_eq!(tags.len().get(), 2);
+///
+/// assert!(NonEmptyVec::<i32>::try_from(Vec::new()).is_err());
+/// ```
+#[derive(FromSqlRow, Clone, PartialEq, Eq, Hash)]
+pub struct NonEmptyVec<T>(Vec<T>);
+impl<T> NonEmptyVec<T> {
+    #[must_use]
+    pub fn new(first: T, rest: Vec<T>) -> Self {
+        let mut values = Vec::with_capacity(rest.len() + 1);
+        values.push(first);
+        values.extend(rest);
+        Self(values)
+    }
+
+    /// Creates a new non-empty vector from the items of an iterator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidArray::UnexpectedEmpty`] if the iterator doesn't
+    /// yield any item.
+    pub fn try_from_iter<I: IntoIterator<Item = T>>(iter: I) -> Result<Self, InvalidArray> {
+        Self::try_from(iter.into_iter().collect::<Vec<_>>())
+    }
+
+    #[must_use]
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+
+    /// Returns the first element of the array.
+    #[must_use]
+    pub fn first(&self) -> &T {
+        &self.0[0]
+    }
+
+    /// Returns the last element of the array.
+    #[must_use]
+    pub fn last(&self) -> &T {
+        &self.0[self.0.len() - 1]
+    }
+
+    /// Returns the number of elements in the array, which is never zero.
+    #[must_use]
+    #[expect(clippy::missing_panics_doc, reason = "the vector is never empty")]
+    pub fn len(&self) -> NonZeroUsize {
+        NonZeroUsize::new(self.0.len()).expect("NonEmptyVec is never empty")
+    }
+
+    /// Returns the elements of the array as a slice.
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    /// Returns an iterator over the elements of the array.
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.0.iter()
+    }
+}
+
+impl<T: Debug> Debug for NonEmptyVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl<T> AsRef<[T]> for NonEmptyVec<T> {
+    fn as_ref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T> TryFrom<Vec<T>> for NonEmptyVec<T> {
+    type Error = InvalidArray;
+
+    fn try_from(values: Vec<T>) -> Result<Self, Self::Error> {
+        if values.is_empty() {
+            Err(InvalidArray::UnexpectedEmpty)
+        } else {
+            Ok(Self(values))
+        }
+    }
+}
+
+impl<T> From<NonEmptyVec<T>> for Vec<T> {
+    fn from(values: NonEmptyVec<T>) -> Self {
+        values.0
+    }
+}
+
+impl<T> IntoIterator for NonEmptyVec<T> {
+    type Item = T;
+    type IntoIter = vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a NonEmptyVec<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// A diesel [`Array`](diesel::sql_types::Array) serialization and deserialization wrapper with a bounded length
+///
+/// This type works like benzina [`Array`](crate::Array), except that the
+/// length can be anywhere between `MIN` and `MAX`, both included. The length is
+/// checked when the value is created and when it is read from the database.
+/// To be completely safe, you should also add the following `CHECK`
+/// constraints:
+/// ```sql
+/// array_ndims(array_field) = 1 AND
+/// array_length(array_field, 1) BETWEEN MIN AND MAX AND
+/// array_position(array_field, NULL) IS NULL
+/// ```
+///
+/// ```
+/// use benzina::BoundedVec;
+///
+/// let tags = BoundedVec::<&str, 1, 3>::new(vec!["rust", "sql"]).unwrap();
+/// assert_eq!(tags.as_slice(), ["rust", "sql"]);
+///
+/// assert!(BoundedVec::<&str, 1, 3>::new(Vec::new()).is_err());
+/// assert!(BoundedVec::<&str, 1, 3>::new(vec!["a", "b", "c", "d"]).is_err());
+/// ```
+#[derive(FromSqlRow, Clone, PartialEq, Eq, Hash)]
+pub struct BoundedVec<T, const MIN: usize, const MAX: usize>(Vec<T>);
+impl<T, const MIN: usize, const MAX: usize> BoundedVec<T, MIN, MAX> {
+    /// Creates a new bounded vector.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidArray::UnexpectedLength`] if the length of `values`
+    /// is not between `MIN` and `MAX`.
+    pub fn new(values: Vec<T>) -> Result<Self, InvalidArray> {
+        const { assert!(MIN <= MAX, "MIN must not be greater than MAX") };
+
+        if (MIN..=MAX).contains(&values.len()) {
+            Ok(Self(values))
+        } else {
+            Err(InvalidArray::UnexpectedLength)
+        }
+    }
+
+    /// Creates a new bounded vector from the items of an iterator.
+    ///
+    /// At most `MAX + 1` items are consumed from the iterator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidArray::UnexpectedLength`] if the iterator doesn't
+    /// yield between `MIN` and `MAX` items.
+    pub fn try_from_iter<I: IntoIterator<Item = T>>(iter: I) -> Result<Self, InvalidArray> {
+        Self::new(iter.into_iter().take(MAX.saturating_add(1)).collect())
+    }
+
+    #[must_use]
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+
+    /// Returns the number of elements in the array, between `MIN` and `MAX`.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the array has a length of 0, which is only possible
+    /// if `MIN` is 0.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
 
-            impl<__QS, const N: usize> AppearsOnTable<__QS> for Array<$rust_type, N> {}
+    /// Returns the elements of the array as a slice.
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    /// Returns an iterator over the elements of the array.
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.0.iter()
+    }
+}
+
+impl<T: Debug, const MIN: usize, const MAX: usize> Debug for BoundedVec<T, MIN, MAX> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl<T, const MIN: usize, const MAX: usize> AsRef<[T]> for BoundedVec<T, MIN, MAX> {
+    fn as_ref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T, const MIN: usize, const MAX: usize> TryFrom<Vec<T>> for BoundedVec<T, MIN, MAX> {
+    type Error = InvalidArray;
+
+    fn try_from(values: Vec<T>) -> Result<Self, Self::Error> {
+        Self::new(values)
+    }
+}
+
+impl<T, const MIN: usize, const MAX: usize> From<BoundedVec<T, MIN, MAX>> for Vec<T> {
+    fn from(values: BoundedVec<T, MIN, MAX>) -> Self {
+        values.0
+    }
+}
+
+impl<T, const MIN: usize, const MAX: usize> IntoIterator for BoundedVec<T, MIN, MAX> {
+    type Item = T;
+    type IntoIter = vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, T, const MIN: usize, const MAX: usize> IntoIterator for &'a BoundedVec<T, MIN, MAX> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
 
-            impl<__QS, const N: usize> SelectableExpression<__QS> for Array<$rust_type, N> {}
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
 
-            impl<const N: usize> ToSql<sql_types::Array<Nullable<$diesel_type>>, Pg> for Array<$rust_type, N>
-            {
-                fn to_sql<'b>(
-                    &'b self,
-                    out: &mut diesel::serialize::Output<'b, '_, Pg>,
-                ) -> diesel::serialize::Result {
-                    <[$rust_type] as ToSql<sql_types::Array<$diesel_type>, Pg>>::to_sql(&self.0.as_slice(), out)
-                }
+/// Allows using [`deserialize_as`] and [`serialize_as`] for benzina [`Array`]
+/// and [`ArrayWithNullableItems`] structs.
+///
+/// The type must be a tuple struct wrapping a single `[T; N]` array. Writing
+/// the item type as `Option<T>` selects [`ArrayWithNullableItems`] instead of
+/// [`Array`].
+///
+/// ```
+/// use benzina::{Array, ArrayWithNullableItems, array_deserialize_as};
+///
+/// struct UserFlags([bool; 5]);
+/// struct Scores([Option<i32>; 3]);
+///
+/// array_deserialize_as!(UserFlags => bool, 5);
+/// array_deserialize_as!(Scores => Option<i32>, 3);
+///
+/// let flags = UserFlags::from(Array::new([true, false, true, false, true]));
+/// let array = Array::<bool, 5>::from(flags);
+/// assert_eq!(array.into_inner(), [true, false, true, false, true]);
+///
+/// let scores = Scores::from(ArrayWithNullableItems::new([Some(1), None, Some(3)]));
+/// assert_eq!(scores.0, [Some(1), None, Some(3)]);
+/// ```
+///
+/// [`serialize_as`]: diesel::prelude::Insertable#optional-field-attributes
+/// [`deserialize_as`]: diesel::prelude::Queryable#deserialize_as-attribute
+#[macro_export]
+macro_rules! array_deserialize_as {
+    ($type:ty => Option<$item:ty>, $len:expr $(,)?) => {
+        impl $crate::__private::std::convert::From<$crate::ArrayWithNullableItems<$item, { $len }>>
+            for $type
+        {
+            fn from(value: $crate::ArrayWithNullableItems<$item, { $len }>) -> Self {
+                Self($crate::ArrayWithNullableItems::into_inner(value))
             }
+        }
 
-            impl<const N: usize> FromSql<sql_types::Array<Nullable<$diesel_type>>, Pg> for Array<$rust_type, N>
-            {
-                fn from_sql(bytes: PgValue<'_>) -> diesel::deserialize::Result<Self> {
-                    let raw = <Vec<Option<$rust_type>> as FromSql<sql_types::Array<Nullable<$diesel_type>>, Pg>>::from_sql(bytes)?;
-
-                    let res: [$rust_type; N] = raw
-                        .into_iter()
-                        .collect::<Option<Vec<$rust_type>>>()
-                        .ok_or(diesel::result::Error::DeserializationError(Box::new(
-                            InvalidArray::UnexpectedNullValue,
-                        )))?
-                        .try_into()
-                        .map_err(|_| {
-                            diesel::result::Error::DeserializationError(Box::new(
-                                InvalidArray::UnexpectedLength,
-                            ))
-                        })?;
-
-                    Ok(Self(res))
-                }
+        impl $crate::__private::std::convert::From<$type>
+            for $crate::ArrayWithNullableItems<$item, { $len }>
+        {
+            fn from(value: $type) -> Self {
+                Self::new(value.0)
             }
-        )*
-    }
+        }
+    };
+    ($type:ty => $item:ty, $len:expr $(,)?) => {
+        impl $crate::__private::std::convert::From<$crate::Array<$item, { $len }>> for $type {
+            fn from(value: $crate::Array<$item, { $len }>) -> Self {
+                Self($crate::Array::into_inner(value))
+            }
+        }
+
+        impl $crate::__private::std::convert::From<$type> for $crate::Array<$item, { $len }> {
+            fn from(value: $type) -> Self {
+                Self::new(value.0)
+            }
+        }
+    };
 }
 
-macro_rules! impl_array_with_nullable_items {
+/// An item of the benzina array wrappers
+///
+/// This is implemented for the supported primitives and, when the `array`
+/// feature is enabled, for the enums derived with [`Enum`](crate::Enum) for
+/// PostgreSQL.
+///
+/// ```
+/// use benzina::{Array, ArrayElement};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, benzina::Enum)]
+/// #[benzina(sql_type = schema::sql_types::Animal)]
+/// enum Animal {
+///     Cat,
+///     Dog,
+/// }
+///
+/// mod schema {
+///     pub mod sql_types {
+///         #[derive(diesel::sql_types::SqlType, diesel::query_builder::QueryId)]
+///         #[diesel(postgres_type(name = "animal"))]
+///         pub struct Animal;
+///     }
+/// }
+///
+/// fn assert_array_element<T: ArrayElement>() {}
+/// assert_array_element::<Animal>();
+///
+/// let pets = Array::new([Animal::Cat, Animal::Dog]);
+/// assert_eq!(pets.into_inner(), [Animal::Cat, Animal::Dog]);
+/// ```
+pub trait ArrayElement: Sized {
+    /// The SQL type of the item
+    type SqlType: SqlType + SingleValue;
+
+    /// The name and the OIDs of the PostgreSQL types the items can be read from
+    ///
+    /// `None` skips the check, for types like `Text` that can be read from
+    /// many PostgreSQL types, or types without a fixed OID like enums.
+    const PG_TYPE: Option<(&'static str, &'static [u32])> = None;
+}
+
+macro_rules! impl_array_element {
     (
         $(
-            $rust_type:ident => $diesel_type:ident
+            $rust_type:ty => $diesel_type:ident $(($name:literal, $($oid:literal),+))?
         ),*
     ) => {
         $(
-            impl<const N: usize> Expression for ArrayWithNullableItems<$rust_type, N> {
-                type SqlType = sql_types::Array<Nullable<$diesel_type>>;
+            impl ArrayElement for $rust_type {
+                type SqlType = $diesel_type;
+
+                $(const PG_TYPE: Option<(&'static str, &'static [u32])> = Some(($name, &[$($oid),+]));)?
             }
+        )*
+    };
+}
 
-            impl<const N: usize> QueryId for ArrayWithNullableItems<$rust_type, N> {
-                type QueryId = <sql_types::Array<Nullable<$diesel_type>> as QueryId>::QueryId;
+macro_rules! impl_array_expression {
+    ($type:ident<T $(, const $param:ident: usize)*>) => {
+        impl<T: ArrayElement $(, const $param: usize)*> Expression for $type<T $(, $param)*> {
+            type SqlType = sql_types::Array<Nullable<T::SqlType>>;
+        }
 
-                const HAS_STATIC_QUERY_ID: bool = <sql_types::Array<Nullable<$diesel_type>> as QueryId>::HAS_STATIC_QUERY_ID;
-            }
+        impl<T $(, const $param: usize)*> QueryId for $type<T $(, $param)*>
+        where
+            T: ArrayElement,
+            sql_types::Array<Nullable<T::SqlType>>: QueryId,
+        {
+            type QueryId = <sql_types::Array<Nullable<T::SqlType>> as QueryId>::QueryId;
 
-            impl<const N: usize> QueryFragment<Pg> for ArrayWithNullableItems<$rust_type, N>
-            {
-                fn walk_ast<'b>(&'b self, mut pass: AstPass<'_, 'b, Pg>) -> QueryResult<()> {
-                    pass.push_bind_param(self)?;
-                    Ok(())
-                }
+            const HAS_STATIC_QUERY_ID: bool = <sql_types::Array<Nullable<T::SqlType>> as QueryId>::HAS_STATIC_QUERY_ID;
+        }
+
+        impl<T $(, const $param: usize)*> QueryFragment<Pg> for $type<T $(, $param)*>
+        where
+            T: ArrayElement,
+            Pg: HasSqlType<sql_types::Array<Nullable<T::SqlType>>>,
+            Self: ToSql<sql_types::Array<Nullable<T::SqlType>>, Pg>,
+        {
+            fn walk_ast<'b>(&'b self, mut pass: AstPass<'_, 'b, Pg>) -> QueryResult<()> {
+                pass.push_bind_param(self)?;
+                Ok(())
             }
+        }
 
-            impl<__QS, const N: usize> AppearsOnTable<__QS> for ArrayWithNullableItems<$rust_type, N> {}
-            impl<__QS, const N: usize> SelectableExpression<__QS> for ArrayWithNullableItems<$rust_type, N> {}
+        impl<__QS, T: ArrayElement $(, const $param: usize)*> AppearsOnTable<__QS> for $type<T $(, $param)*> {}
 
+        impl<__QS, T: ArrayElement $(, const $param: usize)*> SelectableExpression<__QS> for $type<T $(, $param)*> {}
+    };
+}
 
-            impl<const N: usize> ToSql<sql_types::Array<Nullable<$diesel_type>>, Pg> for ArrayWithNullableItems<$rust_type, N>
-            {
-                fn to_sql<'b>(
-                    &'b self,
-                    out: &mut diesel::serialize::Output<'b, '_, Pg>,
-                ) -> diesel::serialize::Result {
-                    <[Option<$rust_type>] as ToSql<sql_types::Array<Nullable<$diesel_type>>, Pg>>::to_sql(self.0.as_slice(), out)
-                }
-            }
+impl_array_expression!(Array<T, const N: usize>);
+impl_array_expression!(ArrayWithNullableItems<T, const N: usize>);
+impl_array_expression!(NonEmptyVec<T>);
+impl_array_expression!(BoundedVec<T, const MIN: usize, const MAX: usize>);
 
-            impl<const N: usize> FromSql<sql_types::Array<Nullable<$diesel_type>>, Pg> for ArrayWithNullableItems<$rust_type, N>
-            {
-                fn from_sql(bytes: PgValue<'_>) -> diesel::deserialize::Result<Self> {
-                    let raw = <Vec<Option<$rust_type>> as FromSql<sql_types::Array<Nullable<$diesel_type>>, Pg>>::from_sql(bytes)?;
-
-                    let res: [Option<$rust_type>; N] = raw
-                        .try_into()
-                        .map_err(|_| {
-                            diesel::result::Error::DeserializationError(Box::new(
-                                InvalidArray::UnexpectedLength,
-                            ))
-                        })?;
-
-                    Ok(Self(res))
-                }
-            }
-        )*
+/// Reads the items of an array, checking their type and that none is `NULL`
+fn from_sql_items<T>(bytes: PgValue<'_>) -> diesel::deserialize::Result<Vec<T>>
+where
+    T: ArrayElement,
+    Vec<Option<T>>: FromSql<sql_types::Array<Nullable<T::SqlType>>, Pg>,
+{
+    from_sql_nullable_items(bytes)?
+        .into_iter()
+        .collect::<Option<Vec<T>>>()
+        .ok_or_else(|| InvalidArray::UnexpectedNullValue.into())
+}
+
+/// Reads the items of an array, checking their type
+fn from_sql_nullable_items<T>(bytes: PgValue<'_>) -> diesel::deserialize::Result<Vec<Option<T>>>
+where
+    T: ArrayElement,
+    Vec<Option<T>>: FromSql<sql_types::Array<Nullable<T::SqlType>>, Pg>,
+{
+    if let Some((name, oids)) = T::PG_TYPE {
+        check_element_type(bytes.as_bytes(), name, oids)?;
+    }
+    <Vec<Option<T>> as FromSql<sql_types::Array<Nullable<T::SqlType>>, Pg>>::from_sql(bytes)
+}
+
+impl<T, const N: usize> ToSql<sql_types::Array<Nullable<T::SqlType>>, Pg> for Array<T, N>
+where
+    T: ArrayElement + Debug,
+    [T]: ToSql<sql_types::Array<T::SqlType>, Pg>,
+{
+    fn to_sql<'b>(
+        &'b self,
+        out: &mut diesel::serialize::Output<'b, '_, Pg>,
+    ) -> diesel::serialize::Result {
+        <[T] as ToSql<sql_types::Array<T::SqlType>, Pg>>::to_sql(self.0.as_slice(), out)
+    }
+}
+
+impl<T, const N: usize> FromSql<sql_types::Array<Nullable<T::SqlType>>, Pg> for Array<T, N>
+where
+    T: ArrayElement,
+    Vec<Option<T>>: FromSql<sql_types::Array<Nullable<T::SqlType>>, Pg>,
+{
+    fn from_sql(bytes: PgValue<'_>) -> diesel::deserialize::Result<Self> {
+        let res: [T; N] = from_sql_items(bytes)?
+            .try_into()
+            .map_err(|_| diesel::result::Error::from(InvalidArray::UnexpectedLength))?;
+
+        Ok(Self(res))
+    }
+}
+
+impl<T, const N: usize> ToSql<sql_types::Array<Nullable<T::SqlType>>, Pg>
+    for ArrayWithNullableItems<T, N>
+where
+    T: ArrayElement + Debug,
+    [Option<T>]: ToSql<sql_types::Array<Nullable<T::SqlType>>, Pg>,
+{
+    fn to_sql<'b>(
+        &'b self,
+        out: &mut diesel::serialize::Output<'b, '_, Pg>,
+    ) -> diesel::serialize::Result {
+        <[Option<T>] as ToSql<sql_types::Array<Nullable<T::SqlType>>, Pg>>::to_sql(
+            self.0.as_slice(),
+            out,
+        )
+    }
+}
+
+impl<T, const N: usize> FromSql<sql_types::Array<Nullable<T::SqlType>>, Pg>
+    for ArrayWithNullableItems<T, N>
+where
+    T: ArrayElement,
+    Vec<Option<T>>: FromSql<sql_types::Array<Nullable<T::SqlType>>, Pg>,
+{
+    fn from_sql(bytes: PgValue<'_>) -> diesel::deserialize::Result<Self> {
+        let res: [Option<T>; N] = from_sql_nullable_items(bytes)?
+            .try_into()
+            .map_err(|_| diesel::result::Error::from(InvalidArray::UnexpectedLength))?;
+
+        Ok(Self(res))
+    }
+}
+
+impl<T> ToSql<sql_types::Array<Nullable<T::SqlType>>, Pg> for NonEmptyVec<T>
+where
+    T: ArrayElement + Debug,
+    [T]: ToSql<sql_types::Array<T::SqlType>, Pg>,
+{
+    fn to_sql<'b>(
+        &'b self,
+        out: &mut diesel::serialize::Output<'b, '_, Pg>,
+    ) -> diesel::serialize::Result {
+        <[T] as ToSql<sql_types::Array<T::SqlType>, Pg>>::to_sql(self.0.as_slice(), out)
+    }
+}
+
+impl<T> FromSql<sql_types::Array<Nullable<T::SqlType>>, Pg> for NonEmptyVec<T>
+where
+    T: ArrayElement,
+    Vec<Option<T>>: FromSql<sql_types::Array<Nullable<T::SqlType>>, Pg>,
+{
+    fn from_sql(bytes: PgValue<'_>) -> diesel::deserialize::Result<Self> {
+        Self::try_from(from_sql_items(bytes)?).map_err(Into::into)
+    }
+}
+
+impl<T, const MIN: usize, const MAX: usize> ToSql<sql_types::Array<Nullable<T::SqlType>>, Pg>
+    for BoundedVec<T, MIN, MAX>
+where
+    T: ArrayElement + Debug,
+    [T]: ToSql<sql_types::Array<T::SqlType>, Pg>,
+{
+    fn to_sql<'b>(
+        &'b self,
+        out: &mut diesel::serialize::Output<'b, '_, Pg>,
+    ) -> diesel::serialize::Result {
+        <[T] as ToSql<sql_types::Array<T::SqlType>, Pg>>::to_sql(self.0.as_slice(), out)
+    }
+}
+
+impl<T, const MIN: usize, const MAX: usize> FromSql<sql_types::Array<Nullable<T::SqlType>>, Pg>
+    for BoundedVec<T, MIN, MAX>
+where
+    T: ArrayElement,
+    Vec<Option<T>>: FromSql<sql_types::Array<Nullable<T::SqlType>>, Pg>,
+{
+    fn from_sql(bytes: PgValue<'_>) -> diesel::deserialize::Result<Self> {
+        Self::new(from_sql_items(bytes)?).map_err(Into::into)
+    }
+}
+
+/// Checks the element type OID of a binary encoded array
+///
+/// Items of a different type with the same size would otherwise be silently
+/// reinterpreted, e.g. a `real[]` column read as an `integer[]`. Only the
+/// built-in types are rejected: user-defined types, like a domain over the
+/// expected type, get their OID at runtime and are accepted.
+fn check_element_type(
+    bytes: &[u8],
+    expected: &'static str,
+    oids: &[u32],
+) -> Result<(), InvalidArray> {
+    // the header starts with the number of dimensions and the flags
+    let Some(&oid) = bytes.get(8..).and_then(<[u8]>::first_chunk::<4>) else {
+        // let diesel report the malformed header
+        return Ok(());
     };
+
+    let oid = u32::from_be_bytes(oid);
+    if oid >= FIRST_RUNTIME_OID || oids.contains(&oid) {
+        Ok(())
+    } else {
+        Err(InvalidArray::UnexpectedElementType { expected, oid })
+    }
 }
 
-impl_array! {
-    U15 => SmallInt,
-    U31 => Integer,
-    U63 => BigInt,
-    i16 => SmallInt,
-    i32 => Integer,
-    i64 => BigInt,
-    f32 => Float,
-    f64 => Double,
-    bool => Bool,
-    String => Text
-}
-
-impl_array_with_nullable_items! {
-    U15 => SmallInt,
-    U31 => Integer,
-    U63 => BigInt,
-    i16 => SmallInt,
-    i32 => Integer,
-    i64 => BigInt,
-    f32 => Float,
-    f64 => Double,
-    bool => Bool,
-    String => Text
+/// The first OID that PostgreSQL assigns at runtime rather than to a built-in
+/// type, known as `FirstGenbkiObjectId`
+const FIRST_RUNTIME_OID: u32 = 10_000;
+
+impl_array_element! {
+    U15 => SmallInt ("smallint", 21),
+    U31 => Integer ("integer", 23),
+    U63 => BigInt ("bigint", 20),
+    i16 => SmallInt ("smallint", 21),
+    i32 => Integer ("integer", 23),
+    i64 => BigInt ("bigint", 20),
+    f32 => Float ("real", 700),
+    f64 => Double ("double precision", 701),
+    bool => Bool ("boolean", 16),
+    String => Text,
+    Vec<u8> => Binary ("bytea", 17)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::InvalidArray;
+
+    use super::{Array, ArrayWithNullableItems, BoundedVec, NonEmptyVec, check_element_type};
+
+    fn bytea_round_trip<const N: usize>(array: &Array<Vec<u8>, N>) -> Array<Vec<u8>, N> {
+        use std::num::NonZeroU32;
+
+        use diesel::{
+            deserialize::FromSql,
+            pg::{Pg, PgMetadataLookup, PgTypeMetadata, PgValue},
+            query_builder::{BindCollector, bind_collector::RawBytesBindCollector},
+            sql_types::{self, Binary, Nullable},
+        };
+
+        struct NoLookup;
+
+        impl PgMetadataLookup for NoLookup {
+            fn lookup_type(&mut self, type_name: &str, _schema: Option<&str>) -> PgTypeMetadata {
+                unreachable!("unexpected lookup of `{type_name}`")
+            }
+        }
+
+        let mut collector = RawBytesBindCollector::<Pg>::new();
+        collector
+            .push_bound_value::<sql_types::Array<Nullable<Binary>>, _>(array, &mut NoLookup)
+            .unwrap();
+        let bytes = collector.binds[0].as_deref().unwrap();
+
+        let oid = NonZeroU32::new(1).unwrap();
+        <Array<Vec<u8>, N> as FromSql<sql_types::Array<Nullable<Binary>>, Pg>>::from_sql(
+            PgValue::new(bytes, &oid),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn zero_length() {
+        use std::num::NonZeroU32;
+
+        use diesel::{
+            deserialize::FromSql,
+            pg::{Pg, PgValue},
+            sql_types::{self, Integer, Nullable},
+        };
+
+        fn from_sql(bytes: &[u8]) -> diesel::deserialize::Result<Array<i32, 0>> {
+            let oid = NonZeroU32::new(1).unwrap();
+            <Array<i32, 0> as FromSql<sql_types::Array<Nullable<Integer>>, Pg>>::from_sql(
+                PgValue::new(bytes, &oid),
+            )
+        }
+
+        // no dimensions, no flags and the `int4` element type
+        let empty = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 23];
+        assert!(from_sql(&empty).unwrap().is_empty());
+
+        // one dimension with a single `1` item
+        let single = [
+            0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 23, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 1,
+        ];
+        let err = from_sql(&single).unwrap_err();
+        assert!(matches!(
+            crate::error::as_invalid_array(&*err),
+            Some(InvalidArray::UnexpectedLength)
+        ));
+    }
+
+    #[test]
+    fn len() {
+        assert_eq!(Array::<i32, 3>::new([0; 3]).len(), 3);
+        assert!(!Array::<i32, 3>::new([0; 3]).is_empty());
+        assert!(Array::<i32, 0>::new([]).is_empty());
+
+        assert_eq!(ArrayWithNullableItems::<i32, 2>::new([None; 2]).len(), 2);
+        assert!(!ArrayWithNullableItems::<i32, 2>::new([None; 2]).is_empty());
+        assert!(ArrayWithNullableItems::<i32, 0>::new([]).is_empty());
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!(format!("{:?}", Array::new([1, 2, 3])), "[1, 2, 3]");
+        assert_eq!(
+            format!("{:?}", ArrayWithNullableItems::new([Some(1), None])),
+            "[Some(1), None]"
+        );
+    }
+
+    #[test]
+    fn default() {
+        assert_eq!(Array::<bool, 3>::default().into_inner(), [false; 3]);
+        assert_eq!(
+            Array::<String, 2>::default().into_inner(),
+            [String::new(), String::new()]
+        );
+        assert_eq!(
+            ArrayWithNullableItems::<String, 2>::default().into_inner(),
+            [None, None]
+        );
+    }
+
+    #[test]
+    fn try_from_iter() {
+        assert_eq!(
+            Array::<i32, 3>::try_from_iter(1..=3).unwrap().into_inner(),
+            [1, 2, 3]
+        );
+        assert!(matches!(
+            Array::<i32, 3>::try_from_iter(1..=2),
+            Err(InvalidArray::UnexpectedLength)
+        ));
+        assert!(matches!(
+            Array::<i32, 3>::try_from_iter(1..),
+            Err(InvalidArray::UnexpectedLength)
+        ));
+        assert_eq!(
+            ArrayWithNullableItems::<i32, 2>::try_from_iter([Some(1), None])
+                .unwrap()
+                .into_inner(),
+            [Some(1), None]
+        );
+        assert!(ArrayWithNullableItems::<i32, 2>::try_from_iter([None]).is_err());
+
+        assert_eq!(
+            NonEmptyVec::try_from_iter(1..=2).unwrap().as_slice(),
+            [1, 2]
+        );
+        assert!(matches!(
+            NonEmptyVec::<i32>::try_from_iter([]),
+            Err(InvalidArray::UnexpectedEmpty)
+        ));
+
+        assert_eq!(
+            BoundedVec::<i32, 1, 3>::try_from_iter(1..=3)
+                .unwrap()
+                .as_slice(),
+            [1, 2, 3]
+        );
+        assert!(BoundedVec::<i32, 1, 3>::try_from_iter([]).is_err());
+        assert!(BoundedVec::<i32, 1, 3>::try_from_iter(1..).is_err());
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Array::from([1, 2, 3]), Array::new([1, 2, 3]));
+
+        assert_eq!(
+            Array::<i32, 3>::try_from(vec![1, 2, 3]).unwrap(),
+            Array::new([1, 2, 3])
+        );
+        assert!(matches!(
+            Array::<i32, 3>::try_from(vec![1, 2]),
+            Err(InvalidArray::UnexpectedLength)
+        ));
+        assert!(matches!(
+            Array::<i32, 3>::try_from(vec![1, 2, 3, 4]),
+            Err(InvalidArray::UnexpectedLength)
+        ));
+    }
+
+    #[test]
+    fn nullable_items_helpers() {
+        let present = ArrayWithNullableItems::new([Some(1), Some(2), Some(3)]);
+        assert_eq!(present.count_present(), 3);
+        let doubled = present.map(|n| n * 2);
+        assert_eq!(doubled.as_slice(), [Some(2), Some(4), Some(6)]);
+        assert_eq!(doubled.transpose(), Some(Array::new([2, 4, 6])));
+
+        let missing = ArrayWithNullableItems::new([Some(1), None, Some(3)]);
+        assert_eq!(missing.count_present(), 2);
+        let formatted = missing.map(|n| n.to_string());
+        assert_eq!(
+            formatted.as_slice(),
+            [Some("1".to_owned()), None, Some("3".to_owned())]
+        );
+        assert_eq!(formatted.transpose(), None);
+
+        assert_eq!(
+            ArrayWithNullableItems::<i32, 0>::new([]).transpose(),
+            Some(Array::new([]))
+        );
+    }
+
+    #[test]
+    fn into_iter() {
+        let array = Array::new([1, 2, 3]);
+        assert_eq!((&array).into_iter().sum::<i32>(), 6);
+        assert_eq!(array.into_iter().collect::<Vec<_>>(), [1, 2, 3]);
+
+        let array = ArrayWithNullableItems::new([Some(1), None]);
+        assert_eq!((&array).into_iter().flatten().count(), 1);
+        assert_eq!(array.into_iter().collect::<Vec<_>>(), [Some(1), None]);
+
+        let values = NonEmptyVec::new(1, vec![2]);
+        assert_eq!((&values).into_iter().max(), Some(&2));
+        assert_eq!(values.into_iter().collect::<Vec<_>>(), [1, 2]);
+
+        let values = BoundedVec::<i32, 0, 2>::new(vec![3]).unwrap();
+        assert_eq!((&values).into_iter().next(), Some(&3));
+        assert_eq!(values.into_iter().collect::<Vec<_>>(), [3]);
+    }
+
+    #[test]
+    fn non_empty_vec() {
+        let values = NonEmptyVec::new(1, vec![2, 3]);
+        assert_eq!(*values.first(), 1);
+        assert_eq!(*values.last(), 3);
+        assert_eq!(values.len().get(), 3);
+        assert_eq!(format!("{values:?}"), "[1, 2, 3]");
+
+        let single = NonEmptyVec::new("only", Vec::new());
+        assert_eq!(single.first(), single.last());
+        assert_eq!(single.len().get(), 1);
+
+        assert_eq!(NonEmptyVec::try_from(vec![1, 2, 3]).unwrap(), values);
+        assert!(matches!(
+            NonEmptyVec::<i32>::try_from(Vec::new()),
+            Err(InvalidArray::UnexpectedEmpty)
+        ));
+    }
+
+    #[test]
+    fn bounded_vec() {
+        let values = BoundedVec::<i32, 1, 3>::new(vec![1, 2]).unwrap();
+        assert_eq!(values.len(), 2);
+        assert!(!values.is_empty());
+        assert_eq!(values.as_slice(), [1, 2]);
+        assert_eq!(format!("{values:?}"), "[1, 2]");
+        assert_eq!(Vec::from(values), [1, 2]);
+
+        assert!(BoundedVec::<i32, 1, 3>::try_from(vec![1]).is_ok());
+        assert!(BoundedVec::<i32, 1, 3>::try_from(vec![1, 2, 3]).is_ok());
+        for invalid in [vec![], vec![1, 2, 3, 4]] {
+            assert!(matches!(
+                BoundedVec::<i32, 1, 3>::try_from(invalid),
+                Err(InvalidArray::UnexpectedLength)
+            ));
+        }
+
+        assert!(BoundedVec::<i32, 0, 0>::new(Vec::new()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn element_type() {
+        // one-dimensional `real[]` with a single item
+        let header = [
+            0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0x02, 0xbc, 0, 0, 0, 1, 0, 0, 0, 1,
+        ];
+        assert!(check_element_type(&header, "real", &[700]).is_ok());
+
+        let err = check_element_type(&header, "integer", &[23]).unwrap_err();
+        assert!(matches!(
+            err,
+            InvalidArray::UnexpectedElementType {
+                expected: "integer",
+                oid: 700
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "expected an array of `integer` items, found items of the type with OID 700"
+        );
+
+        // a domain over `integer`, whose OID is assigned at runtime
+        let domain = [
+            0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0x40, 0x01, 0, 0, 0, 1, 0, 0, 0, 1,
+        ];
+        assert!(check_element_type(&domain, "integer", &[23]).is_ok());
+    }
+
+    #[test]
+    fn domain_items() {
+        use std::num::NonZeroU32;
+
+        use diesel::{
+            deserialize::FromSql,
+            pg::{Pg, PgValue},
+            sql_types::{self, Integer, Nullable},
+        };
+
+        use crate::U31;
+
+        // `posint[]`, where `posint` is a domain over `integer` with OID 16385
+        let bytes = [
+            0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0x40, 0x01, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0,
+            7, 0, 0, 0, 4, 0, 0, 0, 9,
+        ];
+        let oid = NonZeroU32::new(1).unwrap();
+        let array = <Array<U31, 2> as FromSql<sql_types::Array<Nullable<Integer>>, Pg>>::from_sql(
+            PgValue::new(&bytes, &oid),
+        )
+        .unwrap();
+        assert_eq!(
+            array.into_inner(),
+            [U31::new(7).unwrap(), U31::new(9).unwrap()]
+        );
+    }
+
+    #[test]
+    fn bytea_items() {
+        let keys = Array::new([vec![], vec![0x00], vec![0xde, 0xad, 0xbe, 0xef]]);
+        assert_eq!(bytea_round_trip(&keys), keys);
+
+        let empty = Vec::<u8>::new();
+        assert_eq!(
+            Array::<Vec<u8>, 2>::default().into_inner(),
+            [empty.clone(), empty]
+        );
+    }
 }
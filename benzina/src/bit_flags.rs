@@ -0,0 +1,238 @@
+use diesel::{
+    deserialize::{FromSql, FromSqlRow},
+    expression::AsExpression,
+    pg::{Pg, PgValue},
+    serialize::{Output, ToSql},
+    sql_types::{BigInt, Integer, SmallInt},
+};
+
+use crate::error::BitFlagsOutOfRange;
+
+/// A set of `BITS` boolean flags packed into an integer column
+///
+/// Flag `i` is stored in the bit of weight `2^i`. The column can be a
+/// `smallint`, an `integer` or a `bigint`, as long as it's wide enough for
+/// `BITS` flags: binding a `BitFlags<20>` to a `smallint` column is a build
+/// error. The sign bit of the column is used like the others, so a
+/// `smallint` holds 16 flags.
+///
+/// Reading a value with bits set beyond the first `BITS` fails with
+/// [`BitFlagsOutOfRange`], like the [`U31`](crate::U31) family does for
+/// negative values.
+///
+/// ```
+/// use benzina::BitFlags;
+///
+/// let mut flags = BitFlags::<10>::EMPTY;
+/// flags.set(1, true);
+/// flags.set(4, true);
+/// assert!(flags.get(4));
+/// assert_eq!(flags.get_bits(), 0b10010);
+/// assert_eq!(flags.iter().collect::<Vec<_>>(), [1, 4]);
+///
+/// assert!(BitFlags::<4>::new(0b1_0000).is_none());
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, FromSqlRow, AsExpression)]
+#[diesel(sql_type = SmallInt)]
+#[diesel(sql_type = Integer)]
+#[diesel(sql_type = BigInt)]
+pub struct BitFlags<const BITS: u32>(u64);
+
+impl<const BITS: u32> BitFlags<BITS> {
+    /// No flag set.
+    pub const EMPTY: Self = Self(0);
+    /// Every flag set.
+    pub const ALL: Self = Self(Self::MASK);
+
+    /// The bits available to the flags
+    const MASK: u64 = {
+        assert!(BITS <= u64::BITS, "`BitFlags` holds at most 64 flags");
+        if BITS == u64::BITS {
+            u64::MAX
+        } else {
+            (1 << BITS) - 1
+        }
+    };
+
+    /// Creates the flags from their bits if no bit beyond the first `BITS` is set.
+    #[must_use]
+    pub const fn new(bits: u64) -> Option<Self> {
+        if bits & !Self::MASK == 0 {
+            Some(Self(bits))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the bits of the flags.
+    #[must_use]
+    pub const fn get_bits(self) -> u64 {
+        self.0
+    }
+
+    /// Returns whether flag `i` is set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not smaller than `BITS`.
+    #[must_use]
+    pub const fn get(self, i: u32) -> bool {
+        assert!(i < BITS, "the flag is out of range");
+        self.0 & (1 << i) != 0
+    }
+
+    /// Sets or clears flag `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not smaller than `BITS`.
+    pub const fn set(&mut self, i: u32, value: bool) {
+        assert!(i < BITS, "the flag is out of range");
+        if value {
+            self.0 |= 1 << i;
+        } else {
+            self.0 &= !(1 << i);
+        }
+    }
+
+    /// Returns an iterator over the indexes of the flags that are set, in
+    /// ascending order.
+    pub fn iter(self) -> impl Iterator<Item = u32> {
+        (0..BITS).filter(move |&i| self.get(i))
+    }
+
+    /// Checks the bits of a value read from the database.
+    fn from_sql_value(bits: u64) -> Result<Self, BitFlagsOutOfRange> {
+        Self::new(bits).ok_or(BitFlagsOutOfRange { bits, flags: BITS })
+    }
+}
+
+macro_rules! impl_sql {
+    ($($sql_type:ident => $signed:ident, $unsigned:ident),*) => {
+        $(
+            impl<const BITS: u32> FromSql<$sql_type, Pg> for BitFlags<BITS> {
+                #[expect(
+                    clippy::cast_sign_loss,
+                    reason = "the sign bit is one of the flags"
+                )]
+                fn from_sql(bytes: PgValue<'_>) -> diesel::deserialize::Result<Self> {
+                    let value = <$signed as FromSql<$sql_type, Pg>>::from_sql(bytes)?;
+                    Self::from_sql_value(u64::from(value as $unsigned)).map_err(Into::into)
+                }
+            }
+
+            impl<const BITS: u32> ToSql<$sql_type, Pg> for BitFlags<BITS> {
+                #[allow(
+                    clippy::cast_possible_truncation,
+                    clippy::cast_possible_wrap,
+                    reason = "`BITS` fits in the column, and the sign bit is one of the flags"
+                )]
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> diesel::serialize::Result {
+                    const {
+                        assert!(
+                            BITS <= $signed::BITS,
+                            concat!("the flags don't fit in a `", stringify!($sql_type), "` column")
+                        );
+                    };
+                    <$signed as ToSql<$sql_type, Pg>>::to_sql(&(self.0 as $unsigned as $signed), &mut out.reborrow())
+                }
+            }
+        )*
+    };
+}
+
+impl_sql! {
+    SmallInt => i16, u16,
+    Integer => i32, u32,
+    BigInt => i64, u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BitFlags;
+
+    #[test]
+    fn set_and_clear() {
+        let mut flags = BitFlags::<12>::EMPTY;
+        flags.set(0, true);
+        flags.set(11, true);
+        flags.set(5, true);
+        assert_eq!(flags.get_bits(), 0b1000_0010_0001);
+        assert_eq!(flags.iter().collect::<Vec<_>>(), [0, 5, 11]);
+
+        flags.set(5, false);
+        flags.set(6, false);
+        assert!(!flags.get(5));
+        assert!(!flags.get(6));
+        assert_eq!(flags.iter().collect::<Vec<_>>(), [0, 11]);
+
+        assert_eq!(BitFlags::<12>::ALL.iter().count(), 12);
+        assert_eq!(BitFlags::<64>::ALL.get_bits(), u64::MAX);
+        assert_eq!(BitFlags::<0>::ALL.get_bits(), 0);
+    }
+
+    #[test]
+    #[should_panic = "the flag is out of range"]
+    fn set_out_of_range() {
+        let mut flags = BitFlags::<8>::EMPTY;
+        flags.set(8, true);
+    }
+
+    #[test]
+    fn out_of_range_bits() {
+        assert_eq!(
+            BitFlags::<3>::new(0b111).map(BitFlags::get_bits),
+            Some(0b111)
+        );
+        assert!(BitFlags::<3>::new(0b1000).is_none());
+
+        let err = BitFlags::<3>::from_sql_value(0b1010).unwrap_err();
+        assert_eq!(err.bits(), 0b1010);
+        assert_eq!(err.flags(), 3);
+        assert_eq!(
+            err.to_string(),
+            "the value 0b1010 sets bits beyond the 3 flags of `BitFlags<3>`"
+        );
+    }
+
+    #[test]
+    fn sign_bit_round_trip() {
+        use std::num::NonZeroU32;
+
+        use diesel::{
+            deserialize::FromSql,
+            pg::{Pg, PgMetadataLookup, PgTypeMetadata, PgValue},
+            query_builder::{BindCollector, bind_collector::RawBytesBindCollector},
+            sql_types::{Integer, SmallInt},
+        };
+
+        struct NoLookup;
+
+        impl PgMetadataLookup for NoLookup {
+            fn lookup_type(&mut self, type_name: &str, _schema: Option<&str>) -> PgTypeMetadata {
+                unreachable!("unexpected lookup of `{type_name}`")
+            }
+        }
+
+        let flags = BitFlags::<16>::new(0x8001).unwrap();
+        let mut collector = RawBytesBindCollector::<Pg>::new();
+        collector
+            .push_bound_value::<SmallInt, _>(&flags, &mut NoLookup)
+            .unwrap();
+        assert_eq!(collector.binds[0].as_deref(), Some(&[0x80, 0x01][..]));
+
+        let oid = NonZeroU32::new(1).unwrap();
+        let read =
+            <BitFlags<16> as FromSql<SmallInt, Pg>>::from_sql(PgValue::new(&[0x80, 0x01], &oid))
+                .unwrap();
+        assert_eq!(read, flags);
+
+        let err =
+            <BitFlags<4> as FromSql<Integer, Pg>>::from_sql(PgValue::new(&[0, 0, 0, 0x10], &oid))
+                .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the value 0b10000 sets bits beyond the 4 flags of `BitFlags<4>`"
+        );
+    }
+}
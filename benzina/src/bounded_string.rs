@@ -0,0 +1,157 @@
+use std::{
+    fmt::{self, Display},
+    io::Write as _,
+    ops::Deref,
+};
+
+use diesel::{
+    deserialize::{self, FromSql, FromSqlRow},
+    expression::AsExpression,
+    pg::{Pg, PgValue},
+    serialize::{self, IsNull, Output, ToSql},
+    sql_types::Text,
+};
+
+use crate::error::InvalidString;
+
+/// A string of at most `N` characters
+///
+/// Represents the strings that a `VARCHAR(N)` PostgreSQL column is able to
+/// store. The length is checked when the value is created and when it is read
+/// from the database, so a too long string is rejected in Rust code instead
+/// of failing with an opaque database error when it is written.
+///
+/// Like PostgreSQL, the length is measured in characters, not in bytes.
+///
+/// ```
+/// use benzina::BoundedString;
+///
+/// let username = BoundedString::<8>::new("ferris").unwrap();
+/// assert_eq!(username.as_str(), "ferris");
+///
+/// assert!(BoundedString::<8>::new("ferris the crab").is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, FromSqlRow, AsExpression)]
+#[diesel(sql_type = Text)]
+pub struct BoundedString<const N: usize>(String);
+
+impl<const N: usize> BoundedString<N> {
+    /// Creates a new value if the string is at most `N` characters long.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is longer than `N` characters.
+    pub fn new(s: impl Into<String>) -> Result<Self, InvalidString> {
+        let s = s.into();
+        // the number of bytes is an upper bound of the number of characters
+        if s.len() <= N || s.chars().count() <= N {
+            Ok(Self(s))
+        } else {
+            Err(InvalidString::TooLong)
+        }
+    }
+
+    /// Returns the string.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the inner string.
+    #[must_use]
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl<const N: usize> Deref for BoundedString<N> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const N: usize> AsRef<str> for BoundedString<N> {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<const N: usize> Display for BoundedString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl<const N: usize> TryFrom<String> for BoundedString<N> {
+    type Error = InvalidString;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for BoundedString<N> {
+    type Error = InvalidString;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl<const N: usize> From<BoundedString<N>> for String {
+    fn from(value: BoundedString<N>) -> Self {
+        value.0
+    }
+}
+
+impl<const N: usize> FromSql<Text, Pg> for BoundedString<N> {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let s = <String as FromSql<Text, Pg>>::from_sql(bytes)?;
+        Self::new(s).map_err(Into::into)
+    }
+}
+
+impl<const N: usize> ToSql<Text, Pg> for BoundedString<N> {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.0.as_bytes())
+            .map(|()| IsNull::No)
+            .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::InvalidString;
+
+    use super::BoundedString;
+
+    #[test]
+    fn new() {
+        assert!(BoundedString::<0>::new("").is_ok());
+        assert!(BoundedString::<3>::new("abc").is_ok());
+        assert!(matches!(
+            BoundedString::<3>::new("abcd"),
+            Err(InvalidString::TooLong)
+        ));
+    }
+
+    #[test]
+    fn counts_characters() {
+        // 3 characters, 6 bytes
+        assert!(BoundedString::<3>::new("àèì").is_ok());
+        assert!(BoundedString::<2>::new("àèì").is_err());
+    }
+
+    #[test]
+    fn conversions() {
+        let s = BoundedString::<5>::try_from("crab").unwrap();
+        assert_eq!(&*s, "crab");
+        assert_eq!(s.len(), 4);
+        assert_eq!(s.to_string(), "crab");
+        assert_eq!(String::from(s), "crab");
+
+        assert!(BoundedString::<5>::try_from("ferris".to_owned()).is_err());
+    }
+}
@@ -0,0 +1,157 @@
+use std::{
+    fmt::{self, Display},
+    hash::{Hash, Hasher},
+    io::Write as _,
+};
+
+use diesel::{
+    deserialize::{self, FromSql, FromSqlRow},
+    expression::AsExpression,
+    pg::{Pg, PgValue},
+    serialize::{self, IsNull, Output, ToSql},
+};
+
+use crate::sql_types;
+
+/// A PostgreSQL `citext` case-insensitive string
+///
+/// The `citext` extension must be installed in the database, e.g. with
+/// `CREATE EXTENSION citext;`, for the type to be resolved.
+///
+/// [`PartialEq`] and [`Hash`] compare the lowercase form of the strings, like
+/// PostgreSQL does, so that in-memory comparisons match the database. The
+/// original case is preserved and returned by [`as_str`](Self::as_str).
+///
+/// ```
+/// use benzina::CiText;
+///
+/// assert_eq!(CiText::from("Ferris"), CiText::from("FERRIS"));
+/// assert_eq!(CiText::from("Ferris").as_str(), "Ferris");
+/// ```
+#[derive(Debug, Clone, Default, FromSqlRow, AsExpression)]
+#[diesel(sql_type = sql_types::CiText)]
+pub struct CiText(pub String);
+
+impl CiText {
+    /// Returns the string with its original case.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the inner string with its original case.
+    #[must_use]
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+
+    fn lowercase_chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.0.chars().flat_map(char::to_lowercase)
+    }
+}
+
+impl PartialEq for CiText {
+    fn eq(&self, other: &Self) -> bool {
+        self.lowercase_chars().eq(other.lowercase_chars())
+    }
+}
+
+impl Eq for CiText {}
+
+impl Hash for CiText {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for c in self.lowercase_chars() {
+            state.write_u32(u32::from(c));
+        }
+        // terminate the sequence like `str` does, so that `("ab", "c")` and
+        // `("a", "bc")` hash differently
+        state.write_u8(0xff);
+    }
+}
+
+impl Display for CiText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl AsRef<str> for CiText {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for CiText {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for CiText {
+    fn from(value: &str) -> Self {
+        Self(value.to_owned())
+    }
+}
+
+impl From<CiText> for String {
+    fn from(value: CiText) -> Self {
+        value.0
+    }
+}
+
+impl FromSql<sql_types::CiText, Pg> for CiText {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        Ok(Self(std::str::from_utf8(bytes.as_bytes())?.to_owned()))
+    }
+}
+
+impl ToSql<sql_types::CiText, Pg> for CiText {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.0.as_bytes())
+            .map(|()| IsNull::No)
+            .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashSet,
+        hash::{BuildHasher, RandomState},
+    };
+
+    use super::CiText;
+
+    #[test]
+    fn case_insensitive_eq() {
+        assert_eq!(CiText("Foo".to_owned()), CiText("foo".to_owned()));
+        assert_eq!(CiText::from("STRASSE"), CiText::from("strasse"));
+        assert_eq!(CiText::from("Ärger"), CiText::from("äRGER"));
+        assert_ne!(CiText::from("foo"), CiText::from("fooo"));
+        assert_ne!(CiText::from("foo"), CiText::from("bar"));
+    }
+
+    #[test]
+    fn case_insensitive_hash() {
+        let state = RandomState::new();
+        assert_eq!(
+            state.hash_one(CiText::from("Foo")),
+            state.hash_one(CiText::from("fOO"))
+        );
+
+        let names = ["Ferris", "ferris", "FERRIS", "Corro"]
+            .into_iter()
+            .map(CiText::from)
+            .collect::<HashSet<_>>();
+        assert_eq!(names.len(), 2);
+        assert!(names.contains(&CiText::from("corro")));
+    }
+
+    #[test]
+    fn preserves_case() {
+        let text = CiText::from("Ferris");
+        assert_eq!(text.as_str(), "Ferris");
+        assert_eq!(text.to_string(), "Ferris");
+        assert_eq!(text.into_inner(), "Ferris");
+    }
+}
@@ -1,7 +1,9 @@
 use std::marker::PhantomData;
 
 use diesel::{
-    AppearsOnTable, Column, Expression, QueryResult, SelectableExpression, Table,
+    AppearsOnTable, Column, Expression, ExpressionMethods as _, QueryResult, SelectableExpression,
+    Table,
+    dsl::Gt,
     expression::{ValidGrouping, is_aggregate::No},
     internal::table_macro::{StaticQueryFragment, StaticQueryFragmentInstance},
     pg::Pg,
@@ -9,7 +11,7 @@ use diesel::{
     query_source::{AppearsInFromClause, Once},
 };
 
-use crate::sql_types::Tid;
+use crate::sql_types::{Tid, TidValue};
 
 // EXPERIMENTAL: not subject to semver
 #[expect(clippy::needless_pass_by_value, reason = "API simplicity")]
@@ -68,3 +70,146 @@ where
         pass.push_identifier(<Self as Column>::NAME)
     }
 }
+
+/// A pagination cursor over the physical order of the rows of a table
+///
+/// Each page is selected with [`filter`](Self::filter) and ordered by the
+/// `ctid`, then the cursor is moved past the last row of the page with
+/// [`advance`](Self::advance). Rows that are updated while paginating get a
+/// new `ctid`, so they may be skipped or returned twice.
+///
+/// This API is experimental and not subject to semver.
+///
+/// ```
+/// use benzina::{CtidCursor, ctid, sql_types::TidValue};
+/// use diesel::{QueryDsl as _, debug_query, pg::Pg};
+///
+/// diesel::table! {
+///     users {
+///         id -> Integer,
+///         name -> Text,
+///     }
+/// }
+///
+/// let mut cursor = CtidCursor::START;
+/// cursor.advance(TidValue {
+///     block_number: 3,
+///     offset_number: 7,
+/// });
+///
+/// let page = users::table
+///     .select((ctid(users::table), users::name))
+///     .filter(cursor.filter(users::table))
+///     .order_by(ctid(users::table))
+///     .limit(100);
+/// assert_eq!(
+///     debug_query::<Pg, _>(&page).to_string(),
+///     r#"SELECT "users"."ctid", "users"."name" FROM "users" WHERE ("users"."ctid" > $1) ORDER BY "users"."ctid" LIMIT $2 -- binds: [TidValue { block_number: 3, offset_number: 7 }, 100]"#
+/// );
+/// ```
+// EXPERIMENTAL: not subject to semver
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CtidCursor {
+    last: TidValue,
+}
+
+impl CtidCursor {
+    /// The cursor before the first page
+    pub const START: Self = Self {
+        last: TidValue {
+            block_number: 0,
+            offset_number: 0,
+        },
+    };
+
+    /// Returns the `ctid` of the last row that was read, or `(0, 0)` before
+    /// the first page, which precedes every row.
+    #[must_use]
+    pub const fn last(&self) -> TidValue {
+        self.last
+    }
+
+    /// Moves the cursor past the row with the `last` `ctid`.
+    pub const fn advance(&mut self, last: TidValue) {
+        self.last = last;
+    }
+
+    /// Filters the rows of `table` that come after the cursor.
+    #[must_use]
+    pub fn filter<T: Table>(&self, table: T) -> Gt<Ctid<T>, TidValue> {
+        ctid(table).gt(self.last)
+    }
+}
+
+impl Default for CtidCursor {
+    fn default() -> Self {
+        Self::START
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use diesel::{
+        deserialize::FromSql,
+        pg::{Pg, PgMetadataLookup, PgTypeMetadata, PgValue},
+        query_builder::{QueryFragment as _, bind_collector::RawBytesBindCollector},
+    };
+
+    use super::CtidCursor;
+    use crate::sql_types::{Tid, TidValue};
+
+    diesel::table! {
+        users {
+            id -> Integer,
+        }
+    }
+
+    struct NoLookup;
+
+    impl PgMetadataLookup for NoLookup {
+        fn lookup_type(&mut self, type_name: &str, _schema: Option<&str>) -> PgTypeMetadata {
+            unreachable!("unexpected lookup of `{type_name}`")
+        }
+    }
+
+    fn decode(bytes: &[u8]) -> TidValue {
+        let oid = NonZeroU32::new(27).unwrap();
+        <TidValue as FromSql<Tid, Pg>>::from_sql(PgValue::new(bytes, &oid)).unwrap()
+    }
+
+    #[test]
+    fn paginate() {
+        // The `ctid`s of each page, as sent by PostgreSQL
+        let pages: [&[[u8; 6]]; 3] = [
+            &[[0, 0, 0, 0, 0, 1], [0, 0, 0, 0, 0, 2]],
+            &[[0, 0, 0, 1, 0, 1], [0, 0, 0, 1, 0, 3]],
+            &[],
+        ];
+
+        let mut cursor = CtidCursor::START;
+        let mut read = Vec::new();
+        for page in pages {
+            // Each page is filtered on the `ctid` of the last row read
+            let mut collector = RawBytesBindCollector::<Pg>::new();
+            cursor
+                .filter(users::table)
+                .collect_binds(&mut collector, &mut NoLookup, &Pg)
+                .unwrap();
+            assert_eq!(
+                decode(collector.binds[0].as_deref().unwrap()),
+                cursor.last()
+            );
+
+            let rows = page.iter().map(|bytes| decode(bytes)).collect::<Vec<_>>();
+            if let Some(&last) = rows.last() {
+                cursor.advance(last);
+            }
+            read.extend(rows);
+        }
+
+        assert_eq!(read, [(0, 1), (0, 2), (1, 1), (1, 3)]);
+        assert_eq!(cursor.last(), (1, 3));
+    }
+}
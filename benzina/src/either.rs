@@ -1,5 +1,8 @@
+use std::fmt::{self, Display};
+
 use diesel::backend::Backend;
 use diesel::expression::ValidGrouping;
+use diesel::expression::expression_types::NotSelectable;
 use diesel::query_builder::{AstPass, QueryFragment, QueryId};
 use diesel::{AppearsOnTable, Expression, QueryResult, SelectableExpression};
 
@@ -10,6 +13,67 @@ pub enum Either<L, R> {
     Right(R),
 }
 
+impl<L, R> Either<L, R> {
+    /// Creates a new `Either` holding the left value.
+    pub const fn left(value: L) -> Self {
+        Self::Left(value)
+    }
+
+    /// Creates a new `Either` holding the right value.
+    pub const fn right(value: R) -> Self {
+        Self::Right(value)
+    }
+
+    /// Applies `f` to the left value, leaving the right value untouched.
+    pub fn map_left<F, L2>(self, f: F) -> Either<L2, R>
+    where
+        F: FnOnce(L) -> L2,
+    {
+        match self {
+            Self::Left(l) => Either::Left(f(l)),
+            Self::Right(r) => Either::Right(r),
+        }
+    }
+
+    /// Applies `f` to the right value, leaving the left value untouched.
+    pub fn map_right<F, R2>(self, f: F) -> Either<L, R2>
+    where
+        F: FnOnce(R) -> R2,
+    {
+        match self {
+            Self::Left(l) => Either::Left(l),
+            Self::Right(r) => Either::Right(f(r)),
+        }
+    }
+
+    /// Applies `f` to the left value or `g` to the right value, depending on
+    /// the active variant.
+    pub fn map_either<F, G, L2, R2>(self, f: F, g: G) -> Either<L2, R2>
+    where
+        F: FnOnce(L) -> L2,
+        G: FnOnce(R) -> R2,
+    {
+        match self {
+            Self::Left(l) => Either::Left(f(l)),
+            Self::Right(r) => Either::Right(g(r)),
+        }
+    }
+}
+
+/// Formats the active side, without any `Left` or `Right` marker.
+impl<L, R> Display for Either<L, R>
+where
+    L: Display,
+    R: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Left(l) => l.fmt(f),
+            Self::Right(r) => r.fmt(f),
+        }
+    }
+}
+
 impl<L, R> Expression for Either<L, R>
 where
     L: Expression,
@@ -60,3 +124,396 @@ impl<L, R> QueryId for Either<L, R> {
     type QueryId = ();
     const HAS_STATIC_QUERY_ID: bool = false;
 }
+
+/// Either type for Diesel query fragments that don't share a SQL type, like
+/// the terms of an `ORDER BY` clause.
+///
+/// Unlike [`Either`], the two sides may have different SQL types, so the
+/// result can't be selected and is only accepted where diesel doesn't care
+/// about the type, e.g. in `order_by` and `then_order_by`.
+///
+/// ```
+/// # #[cfg(feature = "postgres")]
+/// # {
+/// use benzina::EitherFragment;
+/// use diesel::{ExpressionMethods as _, QueryDsl as _, debug_query, pg::Pg};
+///
+/// diesel::table! {
+///     users {
+///         id -> Integer,
+///         name -> Text,
+///     }
+/// }
+///
+/// let by_name = true;
+/// let order = if by_name {
+///     EitherFragment::Left(users::name.asc())
+/// } else {
+///     EitherFragment::Right(users::id.desc())
+/// };
+/// let query = users::table.select(users::id).order_by(order);
+/// assert_eq!(
+///     debug_query::<Pg, _>(&query).to_string(),
+///     r#"SELECT "users"."id" FROM "users" ORDER BY "users"."name" ASC -- binds: []"#
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub enum EitherFragment<L, R> {
+    Left(L),
+    Right(R),
+}
+
+impl<L, R> Expression for EitherFragment<L, R> {
+    type SqlType = NotSelectable;
+}
+
+impl<L, R, QS> AppearsOnTable<QS> for EitherFragment<L, R>
+where
+    L: AppearsOnTable<QS>,
+    R: AppearsOnTable<QS>,
+{
+}
+
+impl<L, R, GB> ValidGrouping<GB> for EitherFragment<L, R>
+where
+    L: ValidGrouping<GB>,
+    R: ValidGrouping<GB, IsAggregate = L::IsAggregate>,
+{
+    type IsAggregate = L::IsAggregate;
+}
+
+impl<L, R, DB> QueryFragment<DB> for EitherFragment<L, R>
+where
+    DB: Backend,
+    L: QueryFragment<DB>,
+    R: QueryFragment<DB>,
+{
+    fn walk_ast<'b>(&'b self, pass: AstPass<'_, 'b, DB>) -> QueryResult<()> {
+        match self {
+            EitherFragment::Left(l) => l.walk_ast(pass),
+            EitherFragment::Right(r) => r.walk_ast(pass),
+        }
+    }
+}
+
+impl<L, R> QueryId for EitherFragment<L, R> {
+    type QueryId = ();
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+macro_rules! impl_either_n {
+    (
+        $(#[$attr:meta])*
+        $name:ident<$first:ident $(, $rest:ident)+>
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy)]
+        pub enum $name<$first, $($rest),+> {
+            $first($first),
+            $($rest($rest)),+
+        }
+
+        impl<$first, $($rest),+> Expression for $name<$first, $($rest),+>
+        where
+            $first: Expression,
+            $($rest: Expression<SqlType = $first::SqlType>),+
+        {
+            type SqlType = $first::SqlType;
+        }
+
+        impl<$first, $($rest,)+ QS> AppearsOnTable<QS> for $name<$first, $($rest),+>
+        where
+            Self: Expression,
+            $first: AppearsOnTable<QS>,
+            $($rest: AppearsOnTable<QS>),+
+        {
+        }
+
+        impl<$first, $($rest,)+ GB> ValidGrouping<GB> for $name<$first, $($rest),+>
+        where
+            $first: ValidGrouping<GB>,
+            $($rest: ValidGrouping<GB, IsAggregate = $first::IsAggregate>),+
+        {
+            type IsAggregate = $first::IsAggregate;
+        }
+
+        impl<$first, $($rest,)+ QS> SelectableExpression<QS> for $name<$first, $($rest),+>
+        where
+            Self: AppearsOnTable<QS>,
+            $first: SelectableExpression<QS>,
+            $($rest: SelectableExpression<QS>),+
+        {
+        }
+
+        impl<$first, $($rest,)+ DB> QueryFragment<DB> for $name<$first, $($rest),+>
+        where
+            DB: Backend,
+            $first: QueryFragment<DB>,
+            $($rest: QueryFragment<DB>),+
+        {
+            fn walk_ast<'b>(&'b self, pass: AstPass<'_, 'b, DB>) -> QueryResult<()> {
+                match self {
+                    Self::$first(inner) => inner.walk_ast(pass),
+                    $(Self::$rest(inner) => inner.walk_ast(pass)),+
+                }
+            }
+        }
+
+        impl<$first, $($rest),+> QueryId for $name<$first, $($rest),+> {
+            type QueryId = ();
+            const HAS_STATIC_QUERY_ID: bool = false;
+        }
+    };
+}
+
+impl_either_n! {
+    /// Either type for Diesel expressions with three variants - allows different expression types
+    /// in match arms.
+    Either3<A, B, C>
+}
+
+impl_either_n! {
+    /// Either type for Diesel expressions with four variants - allows different expression types
+    /// in match arms.
+    Either4<A, B, C, D>
+}
+
+/// Builds an [`Either`], [`Either3`] or [`Either4`] by picking the first arm
+/// whose condition is `true`, falling back to the `else` arm.
+///
+/// Chains with more than four arms are nested into the [`Either::Right`]
+/// variant. Since the arm is only known at runtime, the resulting expression
+/// never has a static [`QueryId`], meaning that diesel won't cache the
+/// prepared statement.
+///
+/// ```
+/// # #[cfg(feature = "postgres")]
+/// # {
+/// use benzina::either_chain;
+/// use diesel::{QueryDsl as _, debug_query, pg::Pg};
+///
+/// diesel::table! {
+///     users {
+///         id -> Integer,
+///         age -> Integer,
+///         score -> Integer,
+///     }
+/// }
+///
+/// let sort_by = "age";
+/// let column = either_chain!(
+///     sort_by == "age" => users::age,
+///     sort_by == "score" => users::score,
+///     else => users::id,
+/// );
+/// let query = users::table.select(column);
+/// assert_eq!(
+///     debug_query::<Pg, _>(&query).to_string(),
+///     r#"SELECT "users"."age" FROM "users" -- binds: []"#
+/// );
+/// # }
+/// ```
+#[macro_export]
+macro_rules! either_chain {
+    ($cond:expr => $a:expr, else => $b:expr $(,)?) => {
+        if $cond {
+            $crate::Either::Left($a)
+        } else {
+            $crate::Either::Right($b)
+        }
+    };
+    ($cond_a:expr => $a:expr, $cond_b:expr => $b:expr, else => $c:expr $(,)?) => {
+        if $cond_a {
+            $crate::Either3::A($a)
+        } else if $cond_b {
+            $crate::Either3::B($b)
+        } else {
+            $crate::Either3::C($c)
+        }
+    };
+    (
+        $cond_a:expr => $a:expr,
+        $cond_b:expr => $b:expr,
+        $cond_c:expr => $c:expr,
+        else => $d:expr $(,)?
+    ) => {
+        if $cond_a {
+            $crate::Either4::A($a)
+        } else if $cond_b {
+            $crate::Either4::B($b)
+        } else if $cond_c {
+            $crate::Either4::C($c)
+        } else {
+            $crate::Either4::D($d)
+        }
+    };
+    ($cond:expr => $a:expr, $($rest:tt)+) => {
+        if $cond {
+            $crate::Either::Left($a)
+        } else {
+            $crate::Either::Right($crate::either_chain!($($rest)+))
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "postgres")]
+    use diesel::{ExpressionMethods as _, QueryDsl as _, debug_query, pg::Pg};
+
+    use super::Either;
+    #[cfg(feature = "postgres")]
+    use super::{Either3, Either4, EitherFragment};
+
+    #[cfg(feature = "postgres")]
+    diesel::table! {
+        users {
+            id -> Integer,
+            age -> Integer,
+            score -> Integer,
+            rank -> Integer,
+            name -> Text,
+        }
+    }
+
+    #[test]
+    fn map() {
+        let left = Either::<i32, &str>::left(1);
+        let right = Either::<i32, &str>::right("one");
+
+        assert!(matches!(left.map_left(|l| l + 1), Either::Left(2)));
+        assert!(matches!(right.map_left(|l| l + 1), Either::Right("one")));
+
+        assert!(matches!(left.map_right(str::len), Either::Left(1)));
+        assert!(matches!(right.map_right(str::len), Either::Right(3)));
+
+        assert!(matches!(
+            left.map_either(|l| l * 2, str::len),
+            Either::Left(2)
+        ));
+        assert!(matches!(
+            right.map_either(|l| l * 2, str::len),
+            Either::Right(3)
+        ));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Either::<i32, &str>::left(1).to_string(), "1");
+        assert_eq!(Either::<i32, &str>::right("one").to_string(), "one");
+        assert_eq!(format!("{:>4}", Either::<i32, &str>::left(7)), "   7");
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn either3_third_branch() {
+        let column = Either3::<users::id, users::age, users::score>::C(users::score);
+        let query = users::table.select(column);
+        assert_eq!(
+            debug_query::<Pg, _>(&query).to_string(),
+            r#"SELECT "users"."score" FROM "users" -- binds: []"#
+        );
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn either4_all_branches() {
+        type Column = Either4<users::id, users::age, users::score, users::rank>;
+
+        for (column, name) in [
+            (Column::A(users::id), "id"),
+            (Column::B(users::age), "age"),
+            (Column::C(users::score), "score"),
+            (Column::D(users::rank), "rank"),
+        ] {
+            let query = users::table.select(column);
+            assert_eq!(
+                debug_query::<Pg, _>(&query).to_string(),
+                format!(r#"SELECT "users"."{name}" FROM "users" -- binds: []"#)
+            );
+        }
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn either_chain() {
+        let two = |n: i32| either_chain!(n == 0 => users::id, else => users::age);
+        let five = |n: i32| {
+            either_chain!(
+                n == 0 => users::id,
+                n == 1 => users::age,
+                n == 2 => users::score,
+                n == 3 => users::rank,
+                else => users::id,
+            )
+        };
+
+        for (n, name) in [(0, "id"), (1, "age")] {
+            assert_eq!(
+                debug_query::<Pg, _>(&users::table.select(two(n))).to_string(),
+                format!(r#"SELECT "users"."{name}" FROM "users" -- binds: []"#)
+            );
+        }
+        for (n, name) in [(0, "id"), (1, "age"), (2, "score"), (3, "rank"), (4, "id")] {
+            assert_eq!(
+                debug_query::<Pg, _>(&users::table.select(five(n))).to_string(),
+                format!(r#"SELECT "users"."{name}" FROM "users" -- binds: []"#)
+            );
+        }
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn either_chain_arities() {
+        let age = 30;
+        assert!(matches!(
+            either_chain!(age < 18 => users::id, age < 65 => users::age, else => users::score),
+            Either3::B(_)
+        ));
+        assert!(matches!(
+            either_chain!(
+                age < 18 => users::id,
+                age < 20 => users::age,
+                age < 25 => users::score,
+                else => users::rank,
+            ),
+            Either4::D(_)
+        ));
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn either_fragment_order_by() {
+        let order = |by_name: bool| {
+            if by_name {
+                EitherFragment::Left((users::name.asc(), users::id.asc()))
+            } else {
+                EitherFragment::Right(users::score.desc())
+            }
+        };
+
+        for (by_name, clause) in [
+            (true, r#""users"."name" ASC, "users"."id" ASC"#),
+            (false, r#""users"."score" DESC"#),
+        ] {
+            let query = users::table.select(users::id).order_by(order(by_name));
+            assert_eq!(
+                debug_query::<Pg, _>(&query).to_string(),
+                format!(r#"SELECT "users"."id" FROM "users" ORDER BY {clause} -- binds: []"#)
+            );
+
+            let query = users::table
+                .select(users::id)
+                .into_boxed::<Pg>()
+                .order_by(users::rank.asc())
+                .then_order_by(order(by_name));
+            assert_eq!(
+                debug_query::<Pg, _>(&query).to_string(),
+                format!(
+                    r#"SELECT "users"."id" FROM "users" ORDER BY "users"."rank" ASC, {clause} -- binds: []"#
+                )
+            );
+        }
+    }
+}
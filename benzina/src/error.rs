@@ -1,26 +1,150 @@
+#[cfg(feature = "postgres")]
+use std::iter;
 use std::{
     error::Error,
     fmt::{self, Display},
 };
 
+/// A value doesn't fit in a benzina integer, like [`U31`](crate::U31)
+#[cfg(feature = "postgres")]
 #[derive(Debug, Copy, Clone)]
 #[non_exhaustive]
-pub struct TryFromIntError;
+pub struct TryFromIntError {
+    pub(crate) value: i128,
+    pub(crate) max: u64,
+}
+
+#[cfg(feature = "postgres")]
+impl TryFromIntError {
+    /// Returns the value that was converted.
+    #[must_use]
+    pub fn value(&self) -> i128 {
+        self.value
+    }
 
+    /// Returns the largest value representable by the benzina integer type.
+    #[must_use]
+    pub fn max(&self) -> u64 {
+        self.max
+    }
+}
+
+#[cfg(feature = "postgres")]
 impl Display for TryFromIntError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str("out of range integral type conversion attempted")
+        write!(
+            f,
+            "out of range integral type conversion attempted: {} is not in 0..={}",
+            self.value, self.max
+        )
     }
 }
 
+#[cfg(feature = "postgres")]
 impl Error for TryFromIntError {}
 
+/// An integer read from the database doesn't fit a benzina integer
+///
+/// Unlike a type mismatch between the column and the Rust type, which diesel
+/// reports while decoding the bytes, this means that the column has the right
+/// type but contains a negative value, usually because the `>= 0` CHECK
+/// constraint is missing.
+#[cfg(feature = "postgres")]
+#[derive(Debug, Copy, Clone)]
+#[non_exhaustive]
+pub struct IntOutOfRange {
+    pub(crate) value: i64,
+    pub(crate) type_name: &'static str,
+    pub(crate) max: u64,
+}
+
+#[cfg(feature = "postgres")]
+impl IntOutOfRange {
+    /// Returns the value that was read.
+    #[must_use]
+    pub fn value(&self) -> i64 {
+        self.value
+    }
+
+    /// Returns the name of the benzina integer type, e.g. `U31`.
+    #[must_use]
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns the largest value representable by the benzina integer type.
+    #[must_use]
+    pub fn max(&self) -> u64 {
+        self.max
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl Display for IntOutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the value {} is out of the `{}` range 0..={}",
+            self.value, self.type_name, self.max
+        )
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl Error for IntOutOfRange {}
+
+/// A [`BitFlags`] value read from the database sets bits beyond its flags
+///
+/// [`BitFlags`]: crate::BitFlags
+#[cfg(feature = "bit-flags")]
+#[derive(Debug, Copy, Clone)]
+pub struct BitFlagsOutOfRange {
+    pub(crate) bits: u64,
+    pub(crate) flags: u32,
+}
+
+#[cfg(feature = "bit-flags")]
+impl BitFlagsOutOfRange {
+    /// Returns the bits that were read, reinterpreted as unsigned.
+    #[must_use]
+    pub fn bits(&self) -> u64 {
+        self.bits
+    }
+
+    /// Returns the number of flags of the [`BitFlags`](crate::BitFlags) type.
+    #[must_use]
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+}
+
+#[cfg(feature = "bit-flags")]
+impl Display for BitFlagsOutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the value {:#b} sets bits beyond the {} flags of `BitFlags<{}>`",
+            self.bits, self.flags, self.flags
+        )
+    }
+}
+
+#[cfg(feature = "bit-flags")]
+impl Error for BitFlagsOutOfRange {}
+
+/// A string isn't a valid benzina integer, like [`U31`](crate::U31)
+///
+/// This enum is `#[non_exhaustive]`, so matching on it requires a wildcard
+/// arm: exhaustive matches written against benzina 0.5 no longer compile.
+#[cfg(feature = "postgres")]
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum ParseIntError {
     Parse(std::num::ParseIntError),
     OutOfRange(TryFromIntError),
 }
 
+#[cfg(feature = "postgres")]
 impl Display for ParseIntError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(match self {
@@ -30,6 +154,7 @@ impl Display for ParseIntError {
     }
 }
 
+#[cfg(feature = "postgres")]
 impl Error for ParseIntError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
@@ -39,19 +164,277 @@ impl Error for ParseIntError {
     }
 }
 
+/// An array read from the database doesn't match the expected shape
+///
+/// When reading through diesel this error is boxed into
+/// [`diesel::result::Error::DeserializationError`], use [`as_invalid_array`]
+/// to recover it.
+///
+/// This enum is `#[non_exhaustive]`, so matching on it requires a wildcard
+/// arm: exhaustive matches written against benzina 0.5 no longer compile.
+#[cfg(feature = "postgres")]
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum InvalidArray {
     UnexpectedLength,
     UnexpectedNullValue,
+    UnexpectedElementType { expected: &'static str, oid: u32 },
+    UnexpectedEmpty,
 }
 
+#[cfg(feature = "postgres")]
 impl Display for InvalidArray {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedLength => f.write_str("mismatched array length"),
+            Self::UnexpectedNullValue => f.write_str("the array contains an unexpected null value"),
+            Self::UnexpectedEmpty => f.write_str("the array is unexpectedly empty"),
+            Self::UnexpectedElementType { expected, oid } => write!(
+                f,
+                "expected an array of `{expected}` items, found items of the type with OID {oid}"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl Error for InvalidArray {}
+
+#[cfg(feature = "postgres")]
+impl From<InvalidArray> for diesel::result::Error {
+    fn from(err: InvalidArray) -> Self {
+        Self::DeserializationError(Box::new(err))
+    }
+}
+
+/// Finds an [`InvalidArray`] in `err` or in any of its sources, looking
+/// through [`diesel::result::Error::DeserializationError`] as well
+///
+/// ```
+/// use benzina::error::{InvalidArray, as_invalid_array};
+///
+/// let err = diesel::result::Error::from(InvalidArray::UnexpectedLength);
+/// assert!(matches!(
+///     as_invalid_array(&err),
+///     Some(InvalidArray::UnexpectedLength)
+/// ));
+/// assert!(as_invalid_array(&diesel::result::Error::NotFound).is_none());
+/// ```
+#[cfg(feature = "postgres")]
+#[must_use]
+pub fn as_invalid_array<'a>(err: &'a (dyn Error + 'static)) -> Option<&'a InvalidArray> {
+    iter::successors(Some(err), |&err| err.source()).find_map(|err| {
+        // diesel doesn't expose the boxed error as the source
+        match err.downcast_ref::<diesel::result::Error>() {
+            Some(diesel::result::Error::DeserializationError(err)) => err.downcast_ref(),
+            _ => err.downcast_ref(),
+        }
+    })
+}
+
+/// A label path isn't a valid [`Ltree`](crate::Ltree)
+#[cfg(feature = "ltree")]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum InvalidLtree {
+    EmptyLabel,
+    LabelTooLong,
+    InvalidCharacter,
+    UnsupportedVersion,
+}
+
+#[cfg(feature = "ltree")]
+impl Display for InvalidLtree {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(match self {
-            Self::UnexpectedLength => "mismatched array length",
-            Self::UnexpectedNullValue => "the array contains an unexpected null value",
+            Self::EmptyLabel => "ltree labels must not be empty",
+            Self::LabelTooLong => "ltree labels must be at most 1000 characters long",
+            Self::InvalidCharacter => "ltree labels may only contain letters, digits, `_` and `-`",
+            Self::UnsupportedVersion => "unsupported ltree binary format version",
         })
     }
 }
 
-impl Error for InvalidArray {}
+#[cfg(feature = "ltree")]
+impl Error for InvalidLtree {}
+
+/// A [`Duration`](std::time::Duration) can't be represented by an
+/// [`Interval`](crate::Interval), or the other way around
+#[cfg(feature = "interval")]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum InvalidDuration {
+    CalendarUnits,
+    Negative,
+    OutOfRange,
+    SubMicrosecond,
+}
+
+#[cfg(feature = "interval")]
+impl Display for InvalidDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::CalendarUnits => "the interval has months or days, which have no fixed duration",
+            Self::Negative => "the interval is negative",
+            Self::OutOfRange => "the duration doesn't fit in an interval",
+            Self::SubMicrosecond => "the duration has a precision finer than a microsecond",
+        })
+    }
+}
+
+#[cfg(feature = "interval")]
+impl Error for InvalidDuration {}
+
+/// A string doesn't fit in a [`BoundedString`](crate::BoundedString)
+#[cfg(feature = "bounded-string")]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum InvalidString {
+    TooLong,
+}
+
+#[cfg(feature = "bounded-string")]
+impl Display for InvalidString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::TooLong => "the string exceeds the maximum length",
+        })
+    }
+}
+
+#[cfg(feature = "bounded-string")]
+impl Error for InvalidString {}
+
+/// A string isn't a valid [`Lsn`](crate::Lsn)
+#[cfg(feature = "lsn")]
+#[derive(Debug, Copy, Clone)]
+#[non_exhaustive]
+pub struct ParseLsnError;
+
+#[cfg(feature = "lsn")]
+impl Display for ParseLsnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid log sequence number, expected the `XXXXXXXX/XXXXXXXX` format")
+    }
+}
+
+#[cfg(feature = "lsn")]
+impl Error for ParseLsnError {}
+
+/// A string isn't a valid [`MacAddr`](crate::MacAddr)
+#[cfg(feature = "network-types")]
+#[derive(Debug, Copy, Clone)]
+#[non_exhaustive]
+pub struct ParseMacAddrError;
+
+#[cfg(feature = "network-types")]
+impl Display for ParseMacAddrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid MAC address, expected colon-separated hexadecimal octets")
+    }
+}
+
+#[cfg(feature = "network-types")]
+impl Error for ParseMacAddrError {}
+
+/// A JSON value has fields unknown to [`StrictJson`](crate::StrictJson)
+#[cfg(feature = "json")]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct UnknownJsonFields {
+    pub(crate) paths: Vec<String>,
+}
+
+#[cfg(feature = "json")]
+impl UnknownJsonFields {
+    /// Returns the paths of the fields that were not recognized.
+    #[must_use]
+    pub fn paths(&self) -> &[String] {
+        &self.paths
+    }
+}
+
+#[cfg(feature = "json")]
+impl Display for UnknownJsonFields {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unknown JSON fields: ")?;
+        for (i, path) in self.paths.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "`{path}`")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "json")]
+impl Error for UnknownJsonFields {}
+
+/// A JSON value isn't valid for [`ValidatedJson`](crate::ValidatedJson)
+#[cfg(feature = "json")]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct InvalidJson {
+    pub(crate) message: String,
+}
+
+#[cfg(feature = "json")]
+impl InvalidJson {
+    /// Returns the message of the failed validation.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+#[cfg(feature = "json")]
+impl Display for InvalidJson {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid JSON value: {}", self.message)
+    }
+}
+
+#[cfg(feature = "json")]
+impl Error for InvalidJson {}
+
+/// A string doesn't match any of the labels of an enum deriving `Enum` with
+/// `#[benzina(labels)]`
+#[cfg(feature = "derive")]
+#[derive(Debug, Clone)]
+pub struct UnknownVariant {
+    pub(crate) value: String,
+    pub(crate) expected: &'static [&'static str],
+}
+
+#[cfg(feature = "derive")]
+impl UnknownVariant {
+    /// Returns the string that was parsed.
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns the labels of the enum.
+    #[must_use]
+    pub fn expected(&self) -> &'static [&'static str] {
+        self.expected
+    }
+}
+
+#[cfg(feature = "derive")]
+impl Display for UnknownVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown variant `{}`, expected one of ", self.value)?;
+        for (i, label) in self.expected.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "`{label}`")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "derive")]
+impl Error for UnknownVariant {}
@@ -0,0 +1,43 @@
+/// Groups rows that are already mapped into a flat struct by a key, like
+/// [`join!`](crate::join) does for the tuples returned by diesel.
+///
+/// `key` receives a reference to each row and returns the key of the group
+/// the row belongs to, while `child` receives the row by value and returns
+/// the item to collect in the group. The groups are returned as
+/// `Vec<(key, Vec<child>)>`, in the order in which their key is first seen,
+/// and the children keep the order of the rows.
+///
+/// ```
+/// use benzina::group_children;
+///
+/// struct CommentRow {
+///     post_id: i32,
+///     comment_id: i32,
+///     message: &'static str,
+/// }
+///
+/// let rows = vec![
+///     CommentRow { post_id: 2, comment_id: 1, message: "first" },
+///     CommentRow { post_id: 1, comment_id: 2, message: "second" },
+///     CommentRow { post_id: 2, comment_id: 3, message: "third" },
+/// ];
+///
+/// let posts = group_children!(
+///     rows,
+///     key = |row| row.post_id,
+///     child = |row| (row.comment_id, row.message),
+/// );
+/// assert_eq!(
+///     posts,
+///     [
+///         (2, vec![(1, "first"), (3, "third")]),
+///         (1, vec![(2, "second")]),
+///     ]
+/// );
+/// ```
+#[macro_export]
+macro_rules! group_children {
+    ($rows:expr, key = $key:expr, child = $child:expr $(,)?) => {
+        $crate::__private::group_children($rows, $key, $child)
+    };
+}
@@ -0,0 +1,273 @@
+use std::{
+    collections::{BTreeMap, btree_map},
+    io::{self, Write},
+};
+
+use diesel::{
+    deserialize::{self, FromSql, FromSqlRow},
+    expression::AsExpression,
+    pg::{Pg, PgValue},
+    serialize::{self, IsNull, Output, ToSql},
+};
+
+use crate::sql_types;
+
+/// A PostgreSQL `hstore` set of key/value pairs
+///
+/// The `hstore` extension must be installed in the database, e.g. with
+/// `CREATE EXTENSION hstore;`, for the type to be resolved.
+///
+/// Keys are unique and sorted, while values may be `NULL`.
+///
+/// ```
+/// use benzina::HStore;
+///
+/// let mut attributes = HStore::new();
+/// attributes.insert("color", Some("red"));
+/// attributes.insert("size", None::<String>);
+///
+/// assert_eq!(attributes.get("color"), Some(Some("red")));
+/// assert_eq!(attributes.get("size"), Some(None));
+/// assert_eq!(attributes.get("weight"), None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, FromSqlRow, AsExpression)]
+#[diesel(sql_type = sql_types::HStore)]
+pub struct HStore(pub BTreeMap<String, Option<String>>);
+
+impl HStore {
+    /// Creates an empty set of key/value pairs.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    /// Returns the value of `key`, or `None` if the key is missing.
+    ///
+    /// The inner [`Option`] is `None` if the value is `NULL`.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<Option<&str>> {
+        self.0.get(key).map(Option::as_deref)
+    }
+
+    /// Returns `true` if `key` is present, even if its value is `NULL`.
+    #[must_use]
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.0.contains_key(key)
+    }
+
+    /// Inserts a key/value pair, returning the previous value of `key` if it was present.
+    pub fn insert<K, V>(&mut self, key: K, value: Option<V>) -> Option<Option<String>>
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.0.insert(key.into(), value.map(Into::into))
+    }
+
+    /// Removes `key`, returning its value if it was present.
+    pub fn remove(&mut self, key: &str) -> Option<Option<String>> {
+        self.0.remove(key)
+    }
+
+    /// Returns an iterator over the key/value pairs, sorted by key.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        self.0
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_deref()))
+    }
+
+    /// Returns the number of key/value pairs.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no key/value pairs.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the inner map.
+    #[must_use]
+    pub fn into_inner(self) -> BTreeMap<String, Option<String>> {
+        self.0
+    }
+}
+
+impl From<BTreeMap<String, Option<String>>> for HStore {
+    fn from(map: BTreeMap<String, Option<String>>) -> Self {
+        Self(map)
+    }
+}
+
+impl From<HStore> for BTreeMap<String, Option<String>> {
+    fn from(hstore: HStore) -> Self {
+        hstore.0
+    }
+}
+
+impl FromIterator<(String, Option<String>)> for HStore {
+    fn from_iter<I: IntoIterator<Item = (String, Option<String>)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for HStore {
+    type Item = (String, Option<String>);
+    type IntoIter = btree_map::IntoIter<String, Option<String>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+fn decode(mut buf: &[u8]) -> deserialize::Result<HStore> {
+    fn read_len(buf: &mut &[u8]) -> deserialize::Result<i32> {
+        let (&len, rest) = buf
+            .split_first_chunk::<4>()
+            .ok_or("invalid hstore length")?;
+        *buf = rest;
+        Ok(i32::from_be_bytes(len))
+    }
+
+    fn read_str(buf: &mut &[u8], len: i32) -> deserialize::Result<String> {
+        let len = usize::try_from(len).map_err(|_| "invalid hstore string length")?;
+        let (s, rest) = buf
+            .split_at_checked(len)
+            .ok_or("invalid hstore string length")?;
+        *buf = rest;
+        Ok(std::str::from_utf8(s)?.to_owned())
+    }
+
+    let count = read_len(&mut buf)?;
+    if count < 0 {
+        return Err("invalid hstore pair count".into());
+    }
+
+    let mut map = BTreeMap::new();
+    for _ in 0..count {
+        let key_len = read_len(&mut buf)?;
+        let key = read_str(&mut buf, key_len)?;
+        let value = match read_len(&mut buf)? {
+            -1 => None,
+            value_len => Some(read_str(&mut buf, value_len)?),
+        };
+        map.insert(key, value);
+    }
+    Ok(HStore(map))
+}
+
+fn encode<W: Write>(hstore: &HStore, out: &mut W) -> io::Result<()> {
+    fn write_str<W: Write>(s: &str, out: &mut W) -> io::Result<()> {
+        let len = i32::try_from(s.len()).map_err(io::Error::other)?;
+        out.write_all(&len.to_be_bytes())?;
+        out.write_all(s.as_bytes())
+    }
+
+    let count = i32::try_from(hstore.len()).map_err(io::Error::other)?;
+    out.write_all(&count.to_be_bytes())?;
+    for (key, value) in hstore.iter() {
+        write_str(key, out)?;
+        match value {
+            Some(value) => write_str(value, out)?,
+            None => out.write_all(&(-1i32).to_be_bytes())?,
+        }
+    }
+    Ok(())
+}
+
+impl FromSql<sql_types::HStore, Pg> for HStore {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        decode(bytes.as_bytes())
+    }
+}
+
+impl ToSql<sql_types::HStore, Pg> for HStore {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        encode(self, out).map(|()| IsNull::No).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HStore, decode, encode};
+
+    fn round_trip(hstore: &HStore) -> Vec<u8> {
+        let mut buf = Vec::new();
+        encode(hstore, &mut buf).unwrap();
+        assert_eq!(&decode(&buf).unwrap(), hstore);
+        buf
+    }
+
+    #[test]
+    fn wire_format() {
+        let mut hstore = HStore::new();
+        hstore.insert("a", Some("xy"));
+        hstore.insert("b", None::<String>);
+        assert_eq!(
+            round_trip(&hstore),
+            [
+                0, 0, 0, 2, // pairs
+                0, 0, 0, 1, b'a', 0, 0, 0, 2, b'x', b'y', // a => xy
+                0, 0, 0, 1, b'b', 0xff, 0xff, 0xff, 0xff, // b => NULL
+            ]
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let hstore = HStore::new();
+        assert_eq!(round_trip(&hstore), [0, 0, 0, 0]);
+        assert!(decode(&[0, 0, 0, 0]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn nulls_and_empty_strings() {
+        let hstore = [
+            ("empty".to_owned(), Some(String::new())),
+            ("null".to_owned(), None),
+            ("unicode".to_owned(), Some("café".to_owned())),
+        ]
+        .into_iter()
+        .collect::<HStore>();
+        round_trip(&hstore);
+        assert_eq!(hstore.get("empty"), Some(Some("")));
+        assert_eq!(hstore.get("null"), Some(None));
+        assert!(hstore.contains_key("null"));
+        assert!(!hstore.contains_key("missing"));
+    }
+
+    #[test]
+    fn map_accessors() {
+        let mut hstore = HStore::new();
+        assert_eq!(hstore.insert("key", Some("old")), None);
+        assert_eq!(
+            hstore.insert("key", Some("new")),
+            Some(Some("old".to_owned()))
+        );
+        hstore.insert("another", None::<String>);
+        assert_eq!(hstore.len(), 2);
+        assert_eq!(
+            hstore.iter().collect::<Vec<_>>(),
+            [("another", None), ("key", Some("new"))]
+        );
+        assert_eq!(hstore.remove("key"), Some(Some("new".to_owned())));
+        assert_eq!(hstore.remove("key"), None);
+        assert_eq!(hstore.len(), 1);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(decode(&[]).is_err());
+        assert!(decode(&[0xff, 0xff, 0xff, 0xff]).is_err());
+        // missing pair
+        assert!(decode(&[0, 0, 0, 1]).is_err());
+        // truncated key
+        assert!(decode(&[0, 0, 0, 1, 0, 0, 0, 2, b'a']).is_err());
+        // invalid key length
+        assert!(decode(&[0, 0, 0, 1, 0xff, 0xff, 0xff, 0xff]).is_err());
+        // missing value
+        assert!(decode(&[0, 0, 0, 1, 0, 0, 0, 1, b'a']).is_err());
+    }
+}
@@ -0,0 +1,241 @@
+use std::{
+    io::{self, Write},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+use diesel::{
+    deserialize::{self, FromSql, FromSqlRow},
+    expression::AsExpression,
+    pg::{Pg, PgValue},
+    serialize::{self, IsNull, Output, ToSql},
+    sql_types,
+};
+
+const PGSQL_AF_INET: u8 = 2;
+const PGSQL_AF_INET6: u8 = PGSQL_AF_INET + 1;
+
+/// A PostgreSQL `inet` host address
+///
+/// Only host addresses are supported: reading an `inet` value carrying a
+/// netmask (like `192.168.0.1/24`) fails instead of silently dropping it.
+///
+/// ```
+/// use std::net::{IpAddr, Ipv4Addr};
+///
+/// use benzina::Inet;
+/// use diesel::Insertable;
+///
+/// #[derive(Debug, Insertable)]
+/// #[diesel(table_name = sessions)]
+/// struct NewSession {
+///     remote_addr: Inet,
+/// }
+///
+/// let new_session = NewSession {
+///     remote_addr: Inet(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+/// };
+///
+/// diesel::table! {
+///     sessions (id) {
+///         id -> Int4,
+///         remote_addr -> Inet,
+///     }
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, AsExpression, FromSqlRow)]
+#[diesel(sql_type = sql_types::Inet)]
+pub struct Inet(pub IpAddr);
+
+/// A PostgreSQL `cidr` network address
+///
+/// The prefix length never exceeds the length of the address, and the address
+/// never has any bit set to the right of the prefix, as PostgreSQL would reject it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, AsExpression, FromSqlRow)]
+#[diesel(sql_type = sql_types::Cidr)]
+pub struct Cidr {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl From<IpAddr> for Inet {
+    fn from(addr: IpAddr) -> Self {
+        Self(addr)
+    }
+}
+
+impl From<Inet> for IpAddr {
+    fn from(inet: Inet) -> Self {
+        inet.0
+    }
+}
+
+impl Cidr {
+    /// Creates a network address, returning `None` if `prefix` is longer than
+    /// the address or if `addr` has bits set to the right of the prefix.
+    #[must_use]
+    pub fn new(addr: IpAddr, prefix: u8) -> Option<Self> {
+        if prefix > max_prefix(addr) {
+            return None;
+        }
+
+        // shifting out the prefix leaves only the host bits
+        let host_bits_are_zero = match addr {
+            IpAddr::V4(addr) => u32::from(addr).checked_shl(prefix.into()).unwrap_or(0) == 0,
+            IpAddr::V6(addr) => u128::from(addr).checked_shl(prefix.into()).unwrap_or(0) == 0,
+        };
+        host_bits_are_zero.then_some(Self { addr, prefix })
+    }
+
+    /// Returns the network address.
+    #[must_use]
+    pub const fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Returns the prefix length.
+    #[must_use]
+    pub const fn prefix(&self) -> u8 {
+        self.prefix
+    }
+}
+
+fn max_prefix(addr: IpAddr) -> u8 {
+    match addr {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    }
+}
+
+fn decode(buf: &[u8], expected_is_cidr: bool) -> deserialize::Result<(IpAddr, u8)> {
+    let (&[family, bits, is_cidr, len], addr) = buf
+        .split_first_chunk::<4>()
+        .ok_or("invalid network address header")?;
+    if (is_cidr != 0) != expected_is_cidr {
+        return Err("unexpected network address type".into());
+    }
+
+    let addr = match family {
+        PGSQL_AF_INET if len == 4 => IpAddr::V4(Ipv4Addr::from(
+            <[u8; 4]>::try_from(addr).map_err(|_| "invalid IPv4 address")?,
+        )),
+        PGSQL_AF_INET6 if len == 16 => IpAddr::V6(Ipv6Addr::from(
+            <[u8; 16]>::try_from(addr).map_err(|_| "invalid IPv6 address")?,
+        )),
+        _ => return Err("invalid network address family".into()),
+    };
+    if bits > max_prefix(addr) {
+        return Err("invalid network address prefix".into());
+    }
+    Ok((addr, bits))
+}
+
+fn encode<W: Write>(addr: IpAddr, bits: u8, is_cidr: bool, out: &mut W) -> io::Result<()> {
+    match addr {
+        IpAddr::V4(addr) => {
+            out.write_all(&[PGSQL_AF_INET, bits, u8::from(is_cidr), 4])?;
+            out.write_all(&addr.octets())
+        }
+        IpAddr::V6(addr) => {
+            out.write_all(&[PGSQL_AF_INET6, bits, u8::from(is_cidr), 16])?;
+            out.write_all(&addr.octets())
+        }
+    }
+}
+
+impl FromSql<sql_types::Inet, Pg> for Inet {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let (addr, bits) = decode(bytes.as_bytes(), false)?;
+        if bits != max_prefix(addr) {
+            return Err("inet values with a netmask are not supported".into());
+        }
+        Ok(Self(addr))
+    }
+}
+
+impl ToSql<sql_types::Inet, Pg> for Inet {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        encode(self.0, max_prefix(self.0), false, out)
+            .map(|()| IsNull::No)
+            .map_err(Into::into)
+    }
+}
+
+impl FromSql<sql_types::Cidr, Pg> for Cidr {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let (addr, bits) = decode(bytes.as_bytes(), true)?;
+        Self::new(addr, bits).ok_or_else(|| "invalid cidr value".into())
+    }
+}
+
+impl ToSql<sql_types::Cidr, Pg> for Cidr {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        encode(self.addr, self.prefix, true, out)
+            .map(|()| IsNull::No)
+            .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    use super::{Cidr, decode, encode};
+
+    #[test]
+    fn inet_v4() {
+        let addr = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 10));
+        let mut buf = Vec::new();
+        encode(addr, 32, false, &mut buf).unwrap();
+        assert_eq!(buf, [2, 32, 0, 4, 192, 168, 1, 10]);
+        assert_eq!(decode(&buf, false).unwrap(), (addr, 32));
+        assert!(decode(&buf, true).is_err());
+    }
+
+    #[test]
+    fn inet_v6() {
+        let addr = IpAddr::V6(Ipv6Addr::LOCALHOST);
+        let mut buf = Vec::new();
+        encode(addr, 128, false, &mut buf).unwrap();
+        assert_eq!(buf[..4], [3, 128, 0, 16]);
+        assert_eq!(buf[4..], Ipv6Addr::LOCALHOST.octets());
+        assert_eq!(decode(&buf, false).unwrap(), (addr, 128));
+    }
+
+    #[test]
+    fn cidr_v4() {
+        let addr = IpAddr::V4(Ipv4Addr::new(10, 1, 0, 0));
+        let cidr = Cidr::new(addr, 16).unwrap();
+        let mut buf = Vec::new();
+        encode(cidr.addr(), cidr.prefix(), true, &mut buf).unwrap();
+        assert_eq!(buf, [2, 16, 1, 4, 10, 1, 0, 0]);
+        assert_eq!(decode(&buf, true).unwrap(), (addr, 16));
+
+        assert!(Cidr::new(addr, 15).is_none());
+        assert!(Cidr::new(addr, 33).is_none());
+        assert!(Cidr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0).is_some());
+        assert!(Cidr::new(IpAddr::V4(Ipv4Addr::BROADCAST), 32).is_some());
+    }
+
+    #[test]
+    fn cidr_v6() {
+        let addr = IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0));
+        let cidr = Cidr::new(addr, 32).unwrap();
+        let mut buf = Vec::new();
+        encode(cidr.addr(), cidr.prefix(), true, &mut buf).unwrap();
+        assert_eq!(buf[..4], [3, 32, 1, 16]);
+        assert_eq!(decode(&buf, true).unwrap(), (addr, 32));
+
+        assert!(Cidr::new(addr, 16).is_none());
+        assert!(Cidr::new(addr, 129).is_none());
+        assert!(Cidr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 128).is_some());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(decode(&[], false).is_err());
+        assert!(decode(&[2, 32, 0, 4, 127, 0, 0], false).is_err());
+        assert!(decode(&[2, 33, 0, 4, 127, 0, 0, 1], false).is_err());
+        assert!(decode(&[3, 32, 0, 4, 127, 0, 0, 1], false).is_err());
+        assert!(decode(&[7, 32, 0, 4, 127, 0, 0, 1], false).is_err());
+    }
+}
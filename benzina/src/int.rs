@@ -1,6 +1,6 @@
 use std::{
-    error::Error,
-    fmt::{self, Display},
+    cmp::Ordering,
+    fmt::{self, Binary, Display, LowerHex, Octal, UpperHex},
     str::FromStr,
 };
 
@@ -12,7 +12,7 @@ use diesel::{
     sql_types::{BigInt, Integer, SmallInt},
 };
 
-use crate::error::{ParseIntError, TryFromIntError};
+use crate::error::{IntOutOfRange, ParseIntError, TryFromIntError};
 
 macro_rules! impl_numbers {
     ($($type:ident => $inner:ident, $inner_signed:ident, $sql_type:ident),*) => {
@@ -68,6 +68,41 @@ macro_rules! impl_numbers {
                     }
                 }
 
+                /// Creates a new value from an `i64` if it fits within the valid range.
+                ///
+                /// This applies the same range check as reading the value from the database,
+                /// e.g. to convert the `BigInt` results of a `sql_query`.
+                #[allow(
+                    clippy::cast_lossless,
+                    clippy::cast_possible_wrap,
+                    clippy::cast_possible_truncation,
+                    clippy::cast_sign_loss,
+                    reason = "`MAX` fits in `i64` and `n` is checked to be in range of `$inner`"
+                )]
+                #[must_use]
+                pub const fn checked_from_i64(n: i64) -> Option<Self> {
+                    if n >= 0 && n <= Self::MAX.get() as i64 {
+                        Some(Self(n as $inner))
+                    } else {
+                        None
+                    }
+                }
+
+                /// Maps an arbitrary hash into the `[MIN, MAX]` range, i.e. for sharding or bucketing.
+                ///
+                /// The hash is reduced modulo `MAX + 1`. Since `MAX + 1` is a power of two that
+                /// divides `u64::MAX + 1`, every output is hit by the same number of hashes and the
+                /// reduction doesn't introduce any bias: the result is as uniform as the hash itself.
+                #[allow(
+                    clippy::cast_lossless,
+                    clippy::cast_possible_truncation,
+                    reason = "`MAX + 1` fits in `u64` and the remainder is always in range of `$inner`"
+                )]
+                #[must_use]
+                pub const fn from_hash(hash: u64) -> Self {
+                    Self((hash % (Self::MAX.get() as u64 + 1)) as $inner)
+                }
+
                 /// Returns the value as an unsigned integer.
                 #[must_use]
                 pub const fn get(self) -> $inner {
@@ -137,6 +172,39 @@ macro_rules! impl_numbers {
                     }
                 }
 
+                /// Calculates `self + rhs`, wrapping around at the bounds of the type.
+                ///
+                /// Returns a tuple of the result along with a boolean indicating whether
+                /// an overflow happened.
+                #[must_use]
+                pub const fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+                    // Both operands are at most `MAX`, so the sum fits in `$inner`
+                    let res = self.get() + rhs.get();
+                    (Self(res & Self::MAX.get()), res > Self::MAX.get())
+                }
+
+                /// Calculates `self - rhs`, wrapping around at the bounds of the type.
+                ///
+                /// Returns a tuple of the result along with a boolean indicating whether
+                /// an overflow happened.
+                #[must_use]
+                pub const fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+                    let (res, overflowed) = self.get().overflowing_sub(rhs.get());
+                    (Self(res & Self::MAX.get()), overflowed)
+                }
+
+                /// Calculates `self * rhs`, wrapping around at the bounds of the type.
+                ///
+                /// Returns a tuple of the result along with a boolean indicating whether
+                /// an overflow happened.
+                #[must_use]
+                pub const fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+                    // `MAX + 1` divides the modulus of `$inner`, so wrapping in `$inner`
+                    // first doesn't change the result
+                    let (res, overflowed) = self.get().overflowing_mul(rhs.get());
+                    (Self(res & Self::MAX.get()), overflowed || res > Self::MAX.get())
+                }
+
                 /// Checked integer division. Computes `self / rhs`, returning `None` if `rhs == 0`.
                 #[must_use]
                 pub const fn checked_div(self, rhs: Self) -> Option<Self> {
@@ -145,6 +213,124 @@ macro_rules! impl_numbers {
                         None => None,
                     }
                 }
+
+                /// Checked exponentiation. Computes `self.pow(exp)`, returning `None` if overflow occurred.
+                #[must_use]
+                pub const fn checked_pow(self, exp: u32) -> Option<Self> {
+                    match self.get().checked_pow(exp) {
+                        Some(res) => Self::new(res),
+                        None => None,
+                    }
+                }
+
+                /// Saturating exponentiation. Computes `self.pow(exp)`, saturating at the numeric bounds instead of overflowing.
+                #[must_use]
+                pub const fn saturating_pow(self, exp: u32) -> Self {
+                    match self.checked_pow(exp) {
+                        Some(res) => res,
+                        None => Self::MAX,
+                    }
+                }
+
+                /// Returns the square root of the number, rounded down.
+                #[must_use]
+                pub const fn isqrt(self) -> Self {
+                    Self(self.get().isqrt())
+                }
+
+                /// Returns `true` if and only if `self == 2^k` for some `k`.
+                #[must_use]
+                pub const fn is_power_of_two(self) -> bool {
+                    self.get().is_power_of_two()
+                }
+
+                /// Returns the smallest power of two greater than or equal to `self`, or `None` if
+                /// it is greater than `MAX`.
+                #[must_use]
+                pub const fn checked_next_power_of_two(self) -> Option<Self> {
+                    match self.get().checked_next_power_of_two() {
+                        Some(res) => Self::new(res),
+                        None => None,
+                    }
+                }
+
+                /// Returns the smallest power of two greater than or equal to `self`, saturating at
+                /// `MAX`.
+                ///
+                /// Note that `MAX` is not a power of two, use [`checked_next_power_of_two`] to
+                /// detect the overflow.
+                ///
+                /// [`checked_next_power_of_two`]: Self::checked_next_power_of_two
+                #[must_use]
+                pub const fn next_power_of_two(self) -> Self {
+                    match self.checked_next_power_of_two() {
+                        Some(res) => res,
+                        None => Self::MAX,
+                    }
+                }
+
+                /// Returns the base 2 logarithm of the number, rounded down.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `self` is zero.
+                #[must_use]
+                pub const fn ilog2(self) -> u32 {
+                    self.get().ilog2()
+                }
+
+                /// Returns the base 10 logarithm of the number, rounded down.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `self` is zero.
+                #[must_use]
+                pub const fn ilog10(self) -> u32 {
+                    self.get().ilog10()
+                }
+
+                /// Returns an iterator over every value from `lo` to `hi`, both included.
+                ///
+                /// This is the stable counterpart of iterating over `lo..=hi`, which
+                /// requires the unstable `Step` trait. The iterator is empty if `lo`
+                /// is greater than `hi`, and stops after yielding [`Self::MAX`].
+                pub fn range_inclusive(lo: Self, hi: Self) -> impl Iterator<Item = Self> {
+                    // `RangeInclusive` terminates even when `hi` is the maximum of
+                    // the inner integer, let alone `Self::MAX`
+                    (lo.get()..=hi.get()).map(Self)
+                }
+
+                /// Returns the index of the bucket the number falls in among `n` buckets,
+                /// computed as `self % n`.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `n` is zero.
+                #[allow(
+                    clippy::cast_lossless,
+                    clippy::cast_possible_truncation,
+                    clippy::unnecessary_cast,
+                    reason = "both operands fit in `u64` and the remainder is smaller than `n`"
+                )]
+                #[must_use]
+                pub const fn bucket(self, n: usize) -> usize {
+                    assert!(n != 0, "the number of buckets must not be zero");
+                    (self.get() as u64 % n as u64) as usize
+                }
+
+                /// Formats the number with `,` separating groups of thousands, like `1,234,567`.
+                #[must_use]
+                pub fn to_grouped_string(self) -> String {
+                    group_thousands(&self.get().to_string())
+                }
+
+                /// Builds the error of a failed conversion from `value`.
+                fn out_of_range(value: i128) -> TryFromIntError {
+                    TryFromIntError {
+                        value,
+                        max: Self::MAX.get().into(),
+                    }
+                }
             }
 
             impl FromStr for $type {
@@ -164,6 +350,30 @@ macro_rules! impl_numbers {
                 }
             }
 
+            impl LowerHex for $type {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    LowerHex::fmt(&self.get(), f)
+                }
+            }
+
+            impl UpperHex for $type {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    UpperHex::fmt(&self.get(), f)
+                }
+            }
+
+            impl Octal for $type {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    Octal::fmt(&self.get(), f)
+                }
+            }
+
+            impl Binary for $type {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    Binary::fmt(&self.get(), f)
+                }
+            }
+
             impl Default for $type {
                 fn default() -> Self {
                     const { Self::new(0).unwrap() }
@@ -186,7 +396,7 @@ macro_rules! impl_numbers {
                 type Error = TryFromIntError;
 
                 fn try_from(value: $inner) -> Result<Self, Self::Error> {
-                    Self::new(value).ok_or(TryFromIntError)
+                    Self::new(value).ok_or_else(|| Self::out_of_range(value.into()))
                 }
             }
 
@@ -194,15 +404,29 @@ macro_rules! impl_numbers {
                 type Error = TryFromIntError;
 
                 fn try_from(value: $inner_signed) -> Result<Self, Self::Error> {
-                    Self::new_signed(value).ok_or(TryFromIntError)
+                    Self::new_signed(value).ok_or_else(|| Self::out_of_range(value.into()))
                 }
             }
 
+            // There is no text path: diesel requests every result, `sql_query`
+            // included, in the binary format, and a text value couldn't be told
+            // apart from a binary one, e.g. `"12"` is also a valid `SmallInt`.
             impl FromSql<$sql_type, Pg> for $type {
                 fn from_sql(bytes: PgValue<'_>) -> diesel::deserialize::Result<Self> {
                     let value = <$inner_signed as FromSql<$sql_type, Pg>>::from_sql(bytes)?;
-                    Self::new_signed(value)
-                        .ok_or_else(|| Box::new(TryFromIntError) as Box<dyn Error + Send + Sync + 'static>)
+                    Self::from_sql_value(value).map_err(Into::into)
+                }
+            }
+
+            impl $type {
+                /// Checks the range of a value read from the database.
+                fn from_sql_value(value: $inner_signed) -> Result<Self, IntOutOfRange> {
+                    let value = i64::from(value);
+                    Self::checked_from_i64(value).ok_or(IntOutOfRange {
+                        value,
+                        type_name: stringify!($type),
+                        max: Self::MAX.get().into(),
+                    })
                 }
             }
 
@@ -215,6 +439,18 @@ macro_rules! impl_numbers {
     }
 }
 
+/// Inserts a `,` every three digits, starting from the right
+fn group_thousands(digits: &str) -> String {
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
 macro_rules! from_numbers {
     ($($from:ident => $to:ident),*) => {
         $(
@@ -223,6 +459,30 @@ macro_rules! from_numbers {
                     Self(value.get().into())
                 }
             }
+
+            impl PartialEq<$from> for $to {
+                fn eq(&self, other: &$from) -> bool {
+                    *self == Self::from(*other)
+                }
+            }
+
+            impl PartialEq<$to> for $from {
+                fn eq(&self, other: &$to) -> bool {
+                    $to::from(*self) == *other
+                }
+            }
+
+            impl PartialOrd<$from> for $to {
+                fn partial_cmp(&self, other: &$from) -> Option<Ordering> {
+                    self.partial_cmp(&Self::from(*other))
+                }
+            }
+
+            impl PartialOrd<$to> for $from {
+                fn partial_cmp(&self, other: &$to) -> Option<Ordering> {
+                    $to::from(*self).partial_cmp(other)
+                }
+            }
         )*
     }
 }
@@ -239,6 +499,24 @@ macro_rules! from_primitive_numbers {
     }
 }
 
+macro_rules! try_from_primitive_numbers {
+    ($($from:ident => $to:ident),*) => {
+        $(
+            impl TryFrom<$from> for $to {
+                type Error = TryFromIntError;
+
+                fn try_from(value: $from) -> Result<Self, Self::Error> {
+                    value
+                        .try_into()
+                        .ok()
+                        .and_then(Self::new)
+                        .ok_or_else(|| Self::out_of_range(value.into()))
+                }
+            }
+        )*
+    }
+}
+
 impl_numbers! {
     U15 => u16, i16, SmallInt,
     U31 => u32, i32, Integer,
@@ -260,6 +538,12 @@ from_primitive_numbers! {
     u32 => U63
 }
 
+try_from_primitive_numbers! {
+    u32 => U15,
+    u64 => U15,
+    u64 => U31
+}
+
 #[cfg(test)]
 mod tests {
     use super::{U15, U31, U63};
@@ -312,6 +596,30 @@ mod tests {
         assert!(U63::new_signed(i64::MAX).is_some());
     }
 
+    #[test]
+    fn test_from_hash() {
+        for hash in [
+            0,
+            1,
+            42,
+            0x7fff,
+            0x8000,
+            0xffff_ffff,
+            1 << 63,
+            u64::MAX - 1,
+            u64::MAX,
+        ] {
+            assert!(U15::from_hash(hash) <= U15::MAX);
+            assert!(U31::from_hash(hash) <= U31::MAX);
+            assert!(U63::from_hash(hash) <= U63::MAX);
+        }
+
+        assert_eq!(U15::from_hash(0x8001).get(), 1);
+        assert_eq!(U31::from_hash(u64::MAX), U31::MAX);
+        assert_eq!(U63::from_hash(1 << 63), U63::MIN);
+        assert_eq!(U63::from_hash(42).get(), 42);
+    }
+
     #[test]
     fn test_get_methods() {
         let val = U15::new(1000).unwrap();
@@ -344,6 +652,71 @@ mod tests {
         assert_eq!(None, a.checked_div(U15::new(0).unwrap()));
     }
 
+    #[test]
+    fn test_overflowing_arithmetic() {
+        let one = U15::new(1).unwrap();
+        let two = U15::new(2).unwrap();
+
+        // Addition
+        assert_eq!((U15::new(3).unwrap(), false), one.overflowing_add(two));
+        assert_eq!((U15::MAX, false), U15::MAX.overflowing_add(U15::MIN));
+        // `i16` doesn't overflow, but `U15::MAX` is exceeded
+        assert_eq!((U15::MIN, true), U15::MAX.overflowing_add(one));
+        assert_eq!(
+            (U15::new(U15::MAX.get() - 1).unwrap(), true),
+            U15::MAX.overflowing_add(U15::MAX)
+        );
+        assert_eq!(
+            (U31::MIN, true),
+            U31::MAX.overflowing_add(U31::new(1).unwrap())
+        );
+        assert_eq!(
+            (U63::MIN, true),
+            U63::MAX.overflowing_add(U63::new(1).unwrap())
+        );
+
+        // Subtraction
+        assert_eq!((one, false), two.overflowing_sub(one));
+        assert_eq!((U15::MAX, true), U15::MIN.overflowing_sub(one));
+        assert_eq!((U15::new(2).unwrap(), true), one.overflowing_sub(U15::MAX));
+        assert_eq!(
+            (U63::MAX, true),
+            U63::MIN.overflowing_sub(U63::new(1).unwrap())
+        );
+
+        // Multiplication
+        assert_eq!((U15::new(4).unwrap(), false), two.overflowing_mul(two));
+        // `i16` doesn't overflow, but `U15::MAX` is exceeded
+        let half = U15::new(1 << 14).unwrap();
+        assert_eq!((U15::MIN, true), half.overflowing_mul(two));
+        assert_eq!(
+            (U15::new(U15::MAX.get() - 1).unwrap(), true),
+            U15::MAX.overflowing_mul(two)
+        );
+        // Both `u16` and `U15::MAX` are exceeded
+        assert_eq!((one, true), U15::MAX.overflowing_mul(U15::MAX));
+        assert_eq!(
+            (U31::MIN, true),
+            U31::new(1 << 30)
+                .unwrap()
+                .overflowing_mul(U31::new(2).unwrap())
+        );
+
+        // Consistency with the checked operations
+        for (a, b) in [
+            (0, 0),
+            (1, U15::MAX.get()),
+            (181, 181),
+            (182, 181),
+            (U15::MAX.get(), U15::MAX.get()),
+        ] {
+            let (a, b) = (U15::new(a).unwrap(), U15::new(b).unwrap());
+            assert_eq!(a.checked_add(b).is_none(), a.overflowing_add(b).1);
+            assert_eq!(a.checked_sub(b).is_none(), a.overflowing_sub(b).1);
+            assert_eq!(a.checked_mul(b).is_none(), a.overflowing_mul(b).1);
+        }
+    }
+
     #[test]
     fn test_saturating_arithmetic() {
         let a = U15::new(100).unwrap();
@@ -387,6 +760,54 @@ mod tests {
         assert_eq!("42", U15::new(42).unwrap().to_string());
     }
 
+    #[test]
+    fn test_radix_formatting() {
+        let value = U15::new(255).unwrap();
+        assert_eq!(format!("{value:x}"), "ff");
+        assert_eq!(format!("{value:#X}"), "0xFF");
+        assert_eq!(format!("{value:o}"), "377");
+        assert_eq!(format!("{value:#010b}"), "0b11111111");
+
+        assert_eq!(format!("{:x}", U31::MAX), "7fffffff");
+        assert_eq!(format!("{:X}", U63::MAX), "7FFFFFFFFFFFFFFF");
+        assert_eq!(format!("{:o}", U31::new(8).unwrap()), "10");
+        assert_eq!(format!("{:b}", U63::new(5).unwrap()), "101");
+    }
+
+    #[test]
+    fn test_bucket() {
+        let mut buckets = [0; 4];
+        for n in 0..100 {
+            buckets[U31::new(n).unwrap().bucket(buckets.len())] += 1;
+        }
+        assert_eq!(buckets, [25; 4]);
+
+        assert_eq!(U15::MAX.bucket(1), 0);
+        assert_eq!(U15::new(7).unwrap().bucket(10), 7);
+        assert_eq!(U63::MAX.bucket(10), 7);
+        assert!(U63::MAX.bucket(usize::MAX) < usize::MAX);
+    }
+
+    #[test]
+    #[should_panic = "the number of buckets must not be zero"]
+    fn test_bucket_zero() {
+        let _ = U15::new(1).unwrap().bucket(0);
+    }
+
+    #[test]
+    fn test_grouped_display() {
+        assert_eq!("0", U15::new(0).unwrap().to_grouped_string());
+        assert_eq!("999", U15::new(999).unwrap().to_grouped_string());
+        assert_eq!("1,000", U15::new(1000).unwrap().to_grouped_string());
+        assert_eq!("32,767", U15::MAX.to_grouped_string());
+        assert_eq!("123,456", U31::new(123_456).unwrap().to_grouped_string());
+        assert_eq!(
+            "1,234,567",
+            U31::new(1_234_567).unwrap().to_grouped_string()
+        );
+        assert_eq!("9,223,372,036,854,775,807", U63::MAX.to_grouped_string());
+    }
+
     #[test]
     fn test_conversions() {
         let val = U15::new(1000).unwrap();
@@ -399,19 +820,145 @@ mod tests {
         assert_eq!(U15::new(1000).unwrap(), U15::try_from(1000u16).unwrap());
         assert_eq!(U15::new(1000).unwrap(), U15::try_from(1000i16).unwrap());
         assert!(U15::try_from(40000u16).is_err());
+        let err = U31::try_from(-5i32).unwrap_err();
+        assert_eq!(err.value(), -5);
+        assert_eq!(err.max(), 2_147_483_647);
+        assert_eq!(
+            err.to_string(),
+            "out of range integral type conversion attempted: -5 is not in 0..=2147483647"
+        );
         assert!(U15::try_from(-1i16).is_err());
     }
 
     #[test]
     fn test_from_primitive_numbers() {
-        assert_eq!(U15::new(u8::MAX.into()).unwrap(), u8::MAX.into());
+        assert_eq!(U15::new(u8::MAX.into()).unwrap(), U15::from(u8::MAX));
+
+        assert_eq!(U31::new(u8::MAX.into()).unwrap(), U31::from(u8::MAX));
+        assert_eq!(U31::new(u16::MAX.into()).unwrap(), U31::from(u16::MAX));
+
+        assert_eq!(U63::new(u8::MAX.into()).unwrap(), U63::from(u8::MAX));
+        assert_eq!(U63::new(u16::MAX.into()).unwrap(), U63::from(u16::MAX));
+        assert_eq!(U63::new(u32::MAX.into()).unwrap(), U63::from(u32::MAX));
+    }
+
+    #[test]
+    fn test_try_from_primitive_numbers() {
+        assert_eq!(U15::try_from(32767u32).unwrap(), U15::MAX);
+        assert!(U15::try_from(32768u32).is_err());
+        assert!(U15::try_from(u32::MAX).is_err());
+
+        assert_eq!(U15::try_from(32767u64).unwrap(), U15::MAX);
+        assert!(U15::try_from(32768u64).is_err());
+        assert!(U15::try_from(u64::MAX).is_err());
+
+        assert_eq!(U31::try_from(2_147_483_647u64).unwrap(), U31::MAX);
+        assert!(U31::try_from(2_147_483_648u64).is_err());
+        assert!(U31::try_from(u64::MAX).is_err());
+        assert_eq!(
+            U31::try_from(u64::MAX).unwrap_err().value(),
+            i128::from(u64::MAX)
+        );
+    }
 
-        assert_eq!(U31::new(u8::MAX.into()).unwrap(), u8::MAX.into());
-        assert_eq!(U31::new(u16::MAX.into()).unwrap(), u16::MAX.into());
+    #[test]
+    fn test_pow() {
+        let two = U15::new(2).unwrap();
+        assert_eq!(two.checked_pow(14), U15::new(16384));
+        assert_eq!(two.checked_pow(15), None);
+        assert_eq!(two.saturating_pow(15), U15::MAX);
+        assert_eq!(U63::new(3).unwrap().saturating_pow(2).get(), 9);
+    }
+
+    #[test]
+    fn test_roots_and_logs() {
+        assert_eq!(U31::new(99).unwrap().isqrt().get(), 9);
+        assert_eq!(U63::MAX.isqrt().get(), 3_037_000_499);
+        assert_eq!(U15::new(1024).unwrap().ilog2(), 10);
+        assert_eq!(U15::MAX.ilog2(), 14);
+        assert_eq!(U31::new(999).unwrap().ilog10(), 2);
+        assert!(U15::new(4096).unwrap().is_power_of_two());
+        assert!(!U15::MAX.is_power_of_two());
+    }
+
+    #[test]
+    fn test_next_power_of_two() {
+        assert_eq!(U15::MIN.next_power_of_two().get(), 1);
+        assert_eq!(U15::new(100).unwrap().next_power_of_two().get(), 128);
+        assert_eq!(U15::new(16384).unwrap().next_power_of_two().get(), 16384);
+
+        // the next power of two doesn't fit, since `MAX` is one less than it
+        assert_eq!(U15::new(16385).unwrap().checked_next_power_of_two(), None);
+        assert_eq!(U15::new(16385).unwrap().next_power_of_two(), U15::MAX);
+        assert_eq!(U31::MAX.next_power_of_two(), U31::MAX);
+        assert_eq!(U63::MAX.next_power_of_two(), U63::MAX);
+    }
+
+    #[test]
+    fn test_range_inclusive() {
+        let values =
+            U15::range_inclusive(U15::new(3).unwrap(), U15::new(7).unwrap()).collect::<Vec<_>>();
+        assert_eq!(values.len(), 5);
+        assert_eq!(values.first(), U15::new(3).as_ref());
+        assert_eq!(values.last(), U15::new(7).as_ref());
+
+        let single = U31::range_inclusive(U31::new(4).unwrap(), U31::new(4).unwrap());
+        assert_eq!(single.count(), 1);
+        let empty = U31::range_inclusive(U31::new(5).unwrap(), U31::new(4).unwrap());
+        assert_eq!(empty.count(), 0);
+
+        let top = U63::range_inclusive(U63::new(U63::MAX.get() - 2).unwrap(), U63::MAX)
+            .collect::<Vec<_>>();
+        assert_eq!(top.len(), 3);
+        assert_eq!(top.last(), Some(&U63::MAX));
+
+        assert_eq!(U15::range_inclusive(U15::MIN, U15::MAX).count(), 32768);
+    }
 
-        assert_eq!(U63::new(u8::MAX.into()).unwrap(), u8::MAX.into());
-        assert_eq!(U63::new(u16::MAX.into()).unwrap(), u16::MAX.into());
-        assert_eq!(U63::new(u32::MAX.into()).unwrap(), u32::MAX.into());
+    #[test]
+    fn test_checked_from_i64() {
+        assert_eq!(U15::checked_from_i64(0), Some(U15::MIN));
+        assert_eq!(U15::checked_from_i64(32767), Some(U15::MAX));
+        assert_eq!(U15::checked_from_i64(32768), None);
+        assert_eq!(U15::checked_from_i64(-1), None);
+
+        assert_eq!(U31::checked_from_i64(2_147_483_647), Some(U31::MAX));
+        assert_eq!(U31::checked_from_i64(2_147_483_648), None);
+        assert_eq!(U31::checked_from_i64(i64::MIN), None);
+
+        assert_eq!(U63::checked_from_i64(0), Some(U63::MIN));
+        assert_eq!(U63::checked_from_i64(i64::MAX), Some(U63::MAX));
+        assert_eq!(U63::checked_from_i64(-1), None);
+    }
+
+    #[test]
+    fn test_from_sql_out_of_range() {
+        assert_eq!(U15::from_sql_value(5).unwrap().get(), 5);
+        assert_eq!(
+            U15::from_sql_value(-1).unwrap_err().to_string(),
+            "the value -1 is out of the `U15` range 0..=32767"
+        );
+        let err = U63::from_sql_value(i64::MIN).unwrap_err();
+        assert_eq!(err.value(), i64::MIN);
+        assert_eq!(err.type_name(), "U63");
+        assert_eq!(err.max(), U63::MAX.get());
+    }
+
+    #[test]
+    fn test_from_sql_binary_only() {
+        use std::num::NonZeroU32;
+
+        use diesel::{
+            deserialize::FromSql,
+            pg::{Pg, PgValue},
+            sql_types::{Integer, SmallInt},
+        };
+
+        let oid = NonZeroU32::new(1).unwrap();
+        // A text `"12"` is read as the binary `0x3132`
+        let value = <U15 as FromSql<SmallInt, Pg>>::from_sql(PgValue::new(b"12", &oid)).unwrap();
+        assert_eq!(value.get(), 0x3132);
+        assert!(<U31 as FromSql<Integer, Pg>>::from_sql(PgValue::new(b"12", &oid)).is_err());
     }
 
     #[test]
@@ -426,4 +973,19 @@ mod tests {
         assert!(a <= c);
         assert!(a >= c);
     }
+
+    #[test]
+    fn test_cross_type_comparisons() {
+        let flags = U15::new(100).unwrap();
+        let total = U31::new(100).unwrap();
+        let big = U63::new(1 << 40).unwrap();
+
+        assert_eq!(flags, total);
+        assert_eq!(total, flags);
+        assert_ne!(flags, big);
+        assert!(flags < big);
+        assert!(big > total);
+        assert!(U31::MAX > U15::MAX);
+        assert!(U15::MAX <= U63::MAX);
+    }
 }
@@ -0,0 +1,224 @@
+use std::{io::Write as _, time::Duration};
+
+use diesel::{
+    deserialize::{self, FromSql, FromSqlRow},
+    expression::AsExpression,
+    pg::{Pg, PgValue},
+    serialize::{self, IsNull, Output, ToSql},
+    sql_types,
+};
+
+use crate::error::InvalidDuration;
+
+/// A PostgreSQL `interval`
+///
+/// PostgreSQL keeps months, days and microseconds separate, because the
+/// length of a month and of a day depends on the point in time the interval
+/// is added to: `1 month` may be 28 to 31 days and, across a daylight saving
+/// time change, `1 day` may not be 24 hours. For the same reason this type
+/// is only converted from and to a [`Duration`] when `months` and `days` are zero.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use benzina::Interval;
+///
+/// let interval = Interval::from_microseconds(90_000_000);
+/// assert_eq!(Duration::try_from(interval).unwrap(), Duration::from_secs(90));
+///
+/// let interval = Interval::new(1, 15, 0);
+/// assert!(Duration::try_from(interval).is_err());
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, AsExpression, FromSqlRow)]
+#[diesel(sql_type = sql_types::Interval)]
+pub struct Interval {
+    pub months: i32,
+    pub days: i32,
+    pub microseconds: i64,
+}
+
+impl Interval {
+    /// Creates an interval from its components.
+    #[must_use]
+    pub const fn new(months: i32, days: i32, microseconds: i64) -> Self {
+        Self {
+            months,
+            days,
+            microseconds,
+        }
+    }
+
+    /// Creates an interval of `months` months.
+    #[must_use]
+    pub const fn from_months(months: i32) -> Self {
+        Self::new(months, 0, 0)
+    }
+
+    /// Creates an interval of `days` days.
+    #[must_use]
+    pub const fn from_days(days: i32) -> Self {
+        Self::new(0, days, 0)
+    }
+
+    /// Creates an interval of `microseconds` microseconds.
+    #[must_use]
+    pub const fn from_microseconds(microseconds: i64) -> Self {
+        Self::new(0, 0, microseconds)
+    }
+}
+
+impl TryFrom<Interval> for Duration {
+    type Error = InvalidDuration;
+
+    fn try_from(interval: Interval) -> Result<Self, Self::Error> {
+        if interval.months != 0 || interval.days != 0 {
+            return Err(InvalidDuration::CalendarUnits);
+        }
+
+        let microseconds =
+            u64::try_from(interval.microseconds).map_err(|_| InvalidDuration::Negative)?;
+        Ok(Self::from_micros(microseconds))
+    }
+}
+
+impl TryFrom<Duration> for Interval {
+    type Error = InvalidDuration;
+
+    fn try_from(duration: Duration) -> Result<Self, Self::Error> {
+        if duration.subsec_nanos() % 1000 != 0 {
+            return Err(InvalidDuration::SubMicrosecond);
+        }
+
+        let microseconds =
+            i64::try_from(duration.as_micros()).map_err(|_| InvalidDuration::OutOfRange)?;
+        Ok(Self::from_microseconds(microseconds))
+    }
+}
+
+fn decode(buf: &[u8]) -> deserialize::Result<Interval> {
+    let (&microseconds, buf) = buf
+        .split_first_chunk::<8>()
+        .ok_or("invalid interval microseconds")?;
+    let (&days, buf) = buf
+        .split_first_chunk::<4>()
+        .ok_or("invalid interval days")?;
+    let (&months, buf) = buf
+        .split_first_chunk::<4>()
+        .ok_or("invalid interval months")?;
+    if !buf.is_empty() {
+        return Err("invalid interval length".into());
+    }
+    Ok(Interval {
+        months: i32::from_be_bytes(months),
+        days: i32::from_be_bytes(days),
+        microseconds: i64::from_be_bytes(microseconds),
+    })
+}
+
+fn encode(interval: &Interval) -> [u8; 8 + 4 + 4] {
+    let mut buf = [0u8; 8 + 4 + 4];
+    buf[..8].copy_from_slice(&interval.microseconds.to_be_bytes());
+    buf[8..12].copy_from_slice(&interval.days.to_be_bytes());
+    buf[12..].copy_from_slice(&interval.months.to_be_bytes());
+    buf
+}
+
+impl FromSql<sql_types::Interval, Pg> for Interval {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        decode(bytes.as_bytes())
+    }
+}
+
+impl ToSql<sql_types::Interval, Pg> for Interval {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(&encode(self))
+            .map(|()| IsNull::No)
+            .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::error::InvalidDuration;
+
+    use super::{Interval, decode, encode};
+
+    #[test]
+    fn round_trip() {
+        let interval = Interval::new(14, 3, 3_723_000_000);
+        let buf = encode(&interval);
+        assert_eq!(
+            buf,
+            [0, 0, 0, 0, 0xdd, 0xe8, 0x78, 0xc0, 0, 0, 0, 3, 0, 0, 0, 14]
+        );
+        assert_eq!(decode(&buf).unwrap(), interval);
+    }
+
+    #[test]
+    fn negative() {
+        for interval in [
+            Interval::new(-1, -2, -3),
+            Interval::from_months(-12),
+            Interval::from_days(i32::MIN),
+            Interval::from_microseconds(i64::MIN),
+            Interval::new(1, -30, 86_400_000_000),
+        ] {
+            assert_eq!(decode(&encode(&interval)).unwrap(), interval);
+        }
+
+        assert_eq!(
+            encode(&Interval::from_microseconds(-1)),
+            [
+                0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0, 0, 0, 0, 0
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(decode(&[]).is_err());
+        assert!(decode(&[0; 15]).is_err());
+    }
+
+    #[test]
+    fn exact_length() {
+        assert_eq!(decode(&[0; 16]).unwrap(), Interval::default());
+        assert!(decode(&[0; 15]).is_err());
+        assert!(decode(&[0; 17]).is_err());
+    }
+
+    #[test]
+    fn duration() {
+        assert_eq!(
+            Duration::try_from(Interval::from_microseconds(1_500_000)).unwrap(),
+            Duration::from_millis(1500)
+        );
+        assert!(matches!(
+            Duration::try_from(Interval::from_days(1)),
+            Err(InvalidDuration::CalendarUnits)
+        ));
+        assert!(matches!(
+            Duration::try_from(Interval::from_months(1)),
+            Err(InvalidDuration::CalendarUnits)
+        ));
+        assert!(matches!(
+            Duration::try_from(Interval::from_microseconds(-1)),
+            Err(InvalidDuration::Negative)
+        ));
+
+        assert_eq!(
+            Interval::try_from(Duration::from_secs(60)).unwrap(),
+            Interval::from_microseconds(60_000_000)
+        );
+        assert!(matches!(
+            Interval::try_from(Duration::from_nanos(1)),
+            Err(InvalidDuration::SubMicrosecond)
+        ));
+        assert!(matches!(
+            Interval::try_from(Duration::from_secs(u64::MAX)),
+            Err(InvalidDuration::OutOfRange)
+        ));
+    }
+}
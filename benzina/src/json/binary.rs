@@ -9,7 +9,7 @@ use diesel::{
 };
 use serde_core::{Serialize, de::DeserializeOwned};
 
-use crate::json::convert::{sql_deserialize_binary, sql_serialize_binary};
+use crate::json::convert::{deserialize_jsonb, serialize_jsonb};
 
 /// A diesel [`Jsonb`] serialization and deserialization
 /// wrapper
@@ -22,7 +22,8 @@ use crate::json::convert::{sql_deserialize_binary, sql_serialize_binary};
 /// This type is not intended to be used directly in the model but rather to be
 /// used with diesel [`serialize_as`] and [`deserialize_as`].
 ///
-/// To use [`serialize_as`] you _MUST_ use [`json_deserialize_as`].
+/// To use [`deserialize_as`] you _MUST_ use [`json_deserialize_as`], while
+/// [`serialize_as`] works out of the box through the `From<T>` implementation.
 /// ```
 /// use benzina::{Jsonb, U31, json_deserialize_as};
 /// use diesel::{Insertable, Queryable};
@@ -96,6 +97,58 @@ impl<T> Jsonb<T> {
     }
 }
 
+impl<T> Jsonb<T>
+where
+    T: Serialize,
+{
+    /// Converts the value into a [`serde_json::Value`], e.g. to inspect or
+    /// log it.
+    ///
+    /// A [`serde_json::Value`] can be stored as is with
+    /// `Jsonb::<serde_json::Value>::from`, without going through a typed value.
+    ///
+    /// ```
+    /// use benzina::Jsonb;
+    /// use serde_json::json;
+    ///
+    /// let value = json!({"can_read": true});
+    /// let json = Jsonb::from(value.clone());
+    /// assert_eq!(json.to_value().unwrap(), value);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value can't be serialized, e.g. if it is a
+    /// map with non-string keys.
+    pub fn to_value(&self) -> serde_json::Result<serde_json::Value> {
+        serde_json::to_value(&self.0)
+    }
+
+    /// Compares the JSON representations of the two values, ignoring the
+    /// order of the keys of the objects, e.g. to detect whether a stored
+    /// document actually changed.
+    ///
+    /// Returns `false` if either value can't be converted with
+    /// [`to_value`](Self::to_value).
+    ///
+    /// ```
+    /// use benzina::Jsonb;
+    ///
+    /// let stored: Jsonb<serde_json::Value> =
+    ///     Jsonb::new(serde_json::from_str(r#"{"a": 1, "b": [true]}"#).unwrap());
+    /// let incoming: Jsonb<serde_json::Value> =
+    ///     Jsonb::new(serde_json::from_str(r#"{"b": [true], "a": 1}"#).unwrap());
+    /// assert!(stored.semantic_eq(&incoming));
+    /// ```
+    #[must_use]
+    pub fn semantic_eq(&self, other: &Self) -> bool {
+        match (self.to_value(), other.to_value()) {
+            (Ok(value), Ok(other)) => value == other,
+            _ => false,
+        }
+    }
+}
+
 impl<T> From<T> for Jsonb<T> {
     fn from(value: T) -> Self {
         Self(value)
@@ -113,7 +166,7 @@ where
     T: DeserializeOwned,
 {
     fn from_sql(value: PgValue) -> diesel::deserialize::Result<Self> {
-        sql_deserialize_binary(value).map(Self)
+        deserialize_jsonb(value).map(Self)
     }
 }
 
@@ -122,6 +175,6 @@ where
     T: Debug + Serialize,
 {
     fn to_sql(&self, out: &mut diesel::serialize::Output<Pg>) -> diesel::serialize::Result {
-        sql_serialize_binary(&self.0, out)
+        serialize_jsonb(&self.0, out)
     }
 }
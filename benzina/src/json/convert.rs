@@ -1,4 +1,4 @@
-use std::io::Write as _;
+use std::{cell::RefCell, io::Write};
 
 use diesel::{
     pg::{Pg, PgValue},
@@ -6,10 +6,22 @@ use diesel::{
 };
 use serde_core::{Serialize, de::DeserializeOwned};
 
-/// Allows using [`deserialize_as`] for benzina [`Json`] and [`Jsonb`] structs.
+/// Allows using [`deserialize_as`] for benzina [`Json`], [`Jsonb`],
+/// [`StrictJson`] and [`ValidatedJson`] structs.
+///
+/// Only the deserialize direction is generated: [`serialize_as`] already
+/// works through the generic `From<T>` implementation of the wrappers, which
+/// would conflict with a generated one. `Option<T>` fields should use
+/// [`NullableJson`] or [`NullableJsonb`] instead, which convert from and into
+/// `Option<T>` for any `T` without needing this macro.
 ///
 /// [`Jsonb`]: crate::Jsonb
-/// [`Json`]: crate::Jsonb
+/// [`Json`]: crate::Json
+/// [`StrictJson`]: crate::StrictJson
+/// [`ValidatedJson`]: crate::ValidatedJson
+/// [`NullableJson`]: crate::NullableJson
+/// [`NullableJsonb`]: crate::NullableJsonb
+/// [`serialize_as`]: diesel::prelude::Insertable#optional-field-attributes
 /// [`deserialize_as`]: diesel::prelude::Queryable#deserialize_as-attribute
 #[macro_export]
 macro_rules! json_deserialize_as {
@@ -28,23 +40,67 @@ macro_rules! json_deserialize_as {
                     $crate::Json::into_inner(value)
                 }
             }
+
+            impl $crate::__private::std::convert::From<$crate::StrictJson<$type>> for $type {
+                fn from(value: $crate::StrictJson<$type>) -> Self {
+                    $crate::StrictJson::into_inner(value)
+                }
+            }
+
+            impl $crate::__private::std::convert::From<$crate::ValidatedJson<$type>> for $type {
+                fn from(value: $crate::ValidatedJson<$type>) -> Self {
+                    $crate::ValidatedJson::into_inner(value)
+                }
+            }
         )*
     };
 }
 
-pub(super) fn sql_serialize<T>(
+/// Serializes `value` as a `JSON` column.
+///
+/// This is the serialization used by [`Json`], exposed to implement
+/// [`ToSql`] by hand for types that can't go through the wrapper.
+///
+/// # Errors
+///
+/// Returns an error if `value` can't be serialized.
+///
+/// [`Json`]: crate::Json
+/// [`ToSql`]: diesel::serialize::ToSql
+pub fn serialize_json<T>(
     value: &T,
     out: &mut diesel::serialize::Output<'_, '_, Pg>,
 ) -> diesel::serialize::Result
 where
     T: Serialize,
 {
-    serde_json::to_writer(out, value)
-        .map(|()| IsNull::No)
-        .map_err(Into::into)
+    write_json(value, out)
 }
 
-pub(super) fn sql_serialize_binary<T>(
+/// Serializes `value` as a MySQL `JSON` column.
+#[cfg(feature = "mysql")]
+pub(crate) fn serialize_json_mysql<T>(
+    value: &T,
+    out: &mut diesel::serialize::Output<'_, '_, diesel::mysql::Mysql>,
+) -> diesel::serialize::Result
+where
+    T: Serialize,
+{
+    write_json(value, out)
+}
+
+/// Serializes `value` as a `JSONB` column.
+///
+/// This is the serialization used by [`Jsonb`], exposed to implement
+/// [`ToSql`] by hand for types that can't go through the wrapper.
+///
+/// # Errors
+///
+/// Returns an error if `value` can't be serialized.
+///
+/// [`Jsonb`]: crate::Jsonb
+/// [`ToSql`]: diesel::serialize::ToSql
+pub fn serialize_jsonb<T>(
     value: &T,
     out: &mut diesel::serialize::Output<'_, '_, Pg>,
 ) -> diesel::serialize::Result
@@ -52,9 +108,72 @@ where
     T: Serialize,
 {
     out.write_all(&[1])?;
-    sql_serialize(value, out)
+    serialize_json(value, out)
 }
 
+/// Serializes `value` into the bytes that [`serialize_jsonb`] sends for a
+/// `JSONB` column: the JSONB format version followed by the JSON payload.
+///
+/// This allows reusing the encoding, e.g. to hash the value for change
+/// detection, without serializing it twice.
+///
+/// ```
+/// use benzina::json::to_jsonb_bytes;
+/// use serde_json::json;
+///
+/// let bytes = to_jsonb_bytes(&json!({"can_read": true})).unwrap();
+/// assert_eq!(bytes, b"\x01{\"can_read\":true}");
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if `value` can't be serialized.
+pub fn to_jsonb_bytes<T>(value: &T) -> serde_json::Result<Vec<u8>>
+where
+    T: Serialize,
+{
+    let mut bytes = vec![1];
+    serde_json::to_writer(&mut bytes, value)?;
+    Ok(bytes)
+}
+
+/// Serializes `value` into a buffer reused by every call on the thread, then
+/// copies it into `out` with a single write
+///
+/// Writing straight into `out` grows the bind buffer a few bytes at a time,
+/// reallocating it repeatedly for every row of a bulk insert.
+fn write_json<T, W>(value: &T, out: &mut W) -> diesel::serialize::Result
+where
+    T: Serialize,
+    W: Write,
+{
+    thread_local! {
+        static SCRATCH: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+    }
+
+    SCRATCH.with(|scratch| {
+        // A `Serialize` implementation may itself serialize JSON
+        let Ok(mut scratch) = scratch.try_borrow_mut() else {
+            serde_json::to_writer(&mut *out, value)?;
+            return Ok(IsNull::No);
+        };
+
+        scratch.clear();
+        let result = serde_json::to_writer(&mut *scratch, value);
+        if result.is_ok() {
+            out.write_all(&scratch)?;
+        }
+        // Don't hold on to the memory of an unusually large value
+        if scratch.capacity() > SCRATCH_MAX_CAPACITY {
+            *scratch = Vec::new();
+        }
+        result.map(|()| IsNull::No).map_err(Into::into)
+    })
+}
+
+/// The largest buffer kept by [`write_json`] between calls
+const SCRATCH_MAX_CAPACITY: usize = 1024 * 1024;
+
 pub(crate) fn sql_serialize_binary_raw(
     value: &[u8],
     out: &mut diesel::serialize::Output<'_, '_, Pg>,
@@ -64,14 +183,84 @@ pub(crate) fn sql_serialize_binary_raw(
     Ok(IsNull::No)
 }
 
-pub(super) fn sql_deserialize<T>(value: PgValue<'_>) -> diesel::deserialize::Result<T>
+/// Deserializes a `JSON` column.
+///
+/// This is the deserialization used by [`Json`], exposed to implement
+/// [`FromSql`] by hand for types that can't go through the wrapper.
+///
+/// # Errors
+///
+/// Returns an error if the value isn't valid JSON or doesn't match `T`.
+///
+/// [`Json`]: crate::Json
+/// [`FromSql`]: diesel::deserialize::FromSql
+pub fn deserialize_json<T>(value: PgValue<'_>) -> diesel::deserialize::Result<T>
 where
     T: DeserializeOwned,
 {
     serde_json::from_slice(value.as_bytes()).map_err(Into::into)
 }
 
-pub(super) fn sql_deserialize_binary<T>(value: PgValue<'_>) -> diesel::deserialize::Result<T>
+/// Deserializes a MySQL `JSON` column.
+#[cfg(feature = "mysql")]
+pub(crate) fn deserialize_json_mysql<T>(
+    value: &diesel::mysql::MysqlValue<'_>,
+) -> diesel::deserialize::Result<T>
+where
+    T: DeserializeOwned,
+{
+    serde_json::from_slice(value.as_bytes()).map_err(Into::into)
+}
+
+/// Deserializes a `JSONB` column.
+///
+/// This is the deserialization used by [`Jsonb`], exposed to implement
+/// [`FromSql`] by hand for types that can't go through the wrapper.
+/// The JSONB format version is checked and the JSON payload is deserialized
+/// directly from the column bytes, without going through [`serde_json::Value`].
+///
+/// ```
+/// use benzina::json::deserialize_jsonb;
+/// use diesel::{
+///     deserialize::{self, FromSql, FromSqlRow},
+///     pg::{Pg, PgValue},
+///     sql_types::Jsonb,
+/// };
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, FromSqlRow)]
+/// struct Settings {
+///     theme: String,
+///     notifications: bool,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct StoredSettings {
+///     theme: Option<String>,
+///     notifications: Option<bool>,
+/// }
+///
+/// impl FromSql<Jsonb, Pg> for Settings {
+///     fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
+///         let stored: StoredSettings = deserialize_jsonb(value)?;
+///         Ok(Self {
+///             theme: stored.theme.unwrap_or_else(|| "light".to_owned()),
+///             notifications: stored.notifications.unwrap_or(true),
+///         })
+///     }
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the JSONB version is unsupported, or if the value isn't
+/// valid JSON or doesn't match `T`. PostgreSQL only emits version 1: enable
+/// the `lenient-jsonb` feature to skip the version of data produced by other
+/// tools instead of rejecting it.
+///
+/// [`Jsonb`]: crate::Jsonb
+/// [`FromSql`]: diesel::deserialize::FromSql
+pub fn deserialize_jsonb<T>(value: PgValue<'_>) -> diesel::deserialize::Result<T>
 where
     T: DeserializeOwned,
 {
@@ -82,14 +271,62 @@ where
 pub(crate) fn sql_deserialize_binary_raw<'a>(
     value: &'a PgValue<'_>,
 ) -> diesel::deserialize::Result<&'a [u8]> {
-    let (version, bytes) = value
-        .as_bytes()
-        .split_first()
-        .ok_or("Empty JSONB payload")?;
+    strip_jsonb_version(value.as_bytes())
+}
+
+/// Strips the JSONB format version from `bytes`
+///
+/// Only version 1 is accepted, unless the `lenient-jsonb` feature is enabled,
+/// in which case any version is skipped and the payload is parsed as JSON.
+fn strip_jsonb_version(bytes: &[u8]) -> diesel::deserialize::Result<&[u8]> {
+    let (version, bytes) = bytes.split_first().ok_or("Empty JSONB payload")?;
 
-    if *version != 1 {
-        return Err("Unsupported JSONB encoding version".into());
+    if *version != 1 && cfg!(not(feature = "lenient-jsonb")) {
+        return Err(format!("Unsupported JSONB encoding version {version}").into());
     }
 
     Ok(bytes)
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_core::{Serialize, Serializer};
+
+    use super::{strip_jsonb_version, write_json};
+
+    /// Serializes its inner value to a JSON string through [`write_json`]
+    struct Embedded(Vec<u32>);
+
+    impl Serialize for Embedded {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut inner = Vec::new();
+            write_json(&self.0, &mut inner).unwrap();
+            serializer.serialize_str(std::str::from_utf8(&inner).unwrap())
+        }
+    }
+
+    #[test]
+    fn reused_scratch() {
+        let mut out = Vec::new();
+        write_json(&[1, 2], &mut out).unwrap();
+        write_json(&"three", &mut out).unwrap();
+        write_json(&[Embedded(vec![4, 5])], &mut out).unwrap();
+        assert_eq!(out, br#"[1,2]"three"["[4,5]"]"#);
+    }
+
+    #[test]
+    fn jsonb_version() {
+        assert_eq!(strip_jsonb_version(b"\x01{}").unwrap(), b"{}");
+        assert!(strip_jsonb_version(b"").is_err());
+
+        let unknown = strip_jsonb_version(b"\x02{}");
+        if cfg!(feature = "lenient-jsonb") {
+            assert_eq!(unknown.unwrap(), b"{}");
+        } else {
+            assert_eq!(
+                unknown.unwrap_err().to_string(),
+                "Unsupported JSONB encoding version 2"
+            );
+        }
+    }
+}
@@ -9,11 +9,15 @@ use diesel::{
 };
 use serde_core::{Serialize, de::DeserializeOwned};
 
-use crate::json::convert::{sql_deserialize, sql_serialize};
+pub use self::convert::{
+    deserialize_json, deserialize_jsonb, serialize_json, serialize_jsonb, to_jsonb_bytes,
+};
 
 pub(crate) mod binary;
 pub(crate) mod convert;
 pub(crate) mod nullable;
+pub(crate) mod strict;
+pub(crate) mod validated;
 
 /// A diesel [`Json`] serialization and deserialization
 /// wrapper
@@ -26,7 +30,8 @@ pub(crate) mod nullable;
 /// This type is not intended to be used directly in the model but rather to be
 /// used with diesel [`serialize_as`] and [`deserialize_as`].
 ///
-/// To use [`serialize_as`] you _MUST_ use [`json_deserialize_as`].
+/// To use [`deserialize_as`] you _MUST_ use [`json_deserialize_as`], while
+/// [`serialize_as`] works out of the box through the `From<T>` implementation.
 /// ```
 /// use benzina::{Json, U31, json_deserialize_as};
 /// use diesel::{Insertable, Queryable};
@@ -100,6 +105,58 @@ impl<T> Json<T> {
     }
 }
 
+impl<T> Json<T>
+where
+    T: Serialize,
+{
+    /// Converts the value into a [`serde_json::Value`], e.g. to inspect or
+    /// log it.
+    ///
+    /// A [`serde_json::Value`] can be stored as is with
+    /// `Json::<serde_json::Value>::from`, without going through a typed value.
+    ///
+    /// ```
+    /// use benzina::Json;
+    /// use serde_json::json;
+    ///
+    /// let value = json!({"can_read": true});
+    /// let json = Json::from(value.clone());
+    /// assert_eq!(json.to_value().unwrap(), value);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value can't be serialized, e.g. if it is a
+    /// map with non-string keys.
+    pub fn to_value(&self) -> serde_json::Result<serde_json::Value> {
+        serde_json::to_value(&self.0)
+    }
+
+    /// Compares the JSON representations of the two values, ignoring the
+    /// order of the keys of the objects, e.g. to detect whether a stored
+    /// document actually changed.
+    ///
+    /// Returns `false` if either value can't be converted with
+    /// [`to_value`](Self::to_value).
+    ///
+    /// ```
+    /// use benzina::Json;
+    ///
+    /// let stored: Json<serde_json::Value> =
+    ///     Json::new(serde_json::from_str(r#"{"a": 1, "b": [true]}"#).unwrap());
+    /// let incoming: Json<serde_json::Value> =
+    ///     Json::new(serde_json::from_str(r#"{"b": [true], "a": 1}"#).unwrap());
+    /// assert!(stored.semantic_eq(&incoming));
+    /// ```
+    #[must_use]
+    pub fn semantic_eq(&self, other: &Self) -> bool {
+        match (self.to_value(), other.to_value()) {
+            (Ok(value), Ok(other)) => value == other,
+            _ => false,
+        }
+    }
+}
+
 impl<T> From<T> for Json<T> {
     fn from(value: T) -> Self {
         Self(value)
@@ -117,7 +174,7 @@ where
     T: DeserializeOwned,
 {
     fn from_sql(value: PgValue<'_>) -> diesel::deserialize::Result<Self> {
-        sql_deserialize(value).map(Self)
+        deserialize_json(value).map(Self)
     }
 }
 
@@ -126,6 +183,56 @@ where
     T: Debug + Serialize,
 {
     fn to_sql(&self, out: &mut diesel::serialize::Output<Pg>) -> diesel::serialize::Result {
-        sql_serialize(&self.0, out)
+        serialize_json(&self.0, out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::{ExpressionMethods, QueryDsl, debug_query, pg::Pg};
+    use serde_json::json;
+
+    use crate::{Json, Jsonb};
+
+    diesel::table! {
+        documents (id) {
+            id -> Int4,
+            json -> Nullable<Json>,
+            jsonb -> Nullable<Jsonb>,
+        }
+    }
+
+    #[test]
+    fn nullable_columns() {
+        let json = Json::new(json!({"a": 1}));
+        let jsonb = Jsonb::new(json!([1, 2]));
+
+        let query = documents::table
+            .filter(documents::json.eq(&json))
+            .filter(documents::jsonb.eq(&jsonb))
+            .select(documents::id);
+        assert_eq!(
+            debug_query::<Pg, _>(&query).to_string(),
+            r#"SELECT "documents"."id" FROM "documents" WHERE (("documents"."json" = $1) AND ("documents"."jsonb" = $2)) -- binds: [Json(Object {"a": Number(1)}), Jsonb(Array [Number(1), Number(2)])]"#
+        );
+
+        let query = diesel::insert_into(documents::table)
+            .values((documents::json.eq(json), documents::jsonb.eq(jsonb)));
+        assert_eq!(
+            debug_query::<Pg, _>(&query).to_string(),
+            r#"INSERT INTO "documents" ("json", "jsonb") VALUES ($1, $2) -- binds: [Json(Object {"a": Number(1)}), Jsonb(Array [Number(1), Number(2)])]"#
+        );
+    }
+
+    #[test]
+    fn semantic_eq() {
+        let json = Json::new(json!({"a": 1, "b": {"c": [1, 2], "d": null}}));
+        let reordered = Json::new(json!({"b": {"d": null, "c": [1, 2]}, "a": 1}));
+        assert!(json.semantic_eq(&reordered));
+        assert!(!json.semantic_eq(&Json::new(json!({"a": 1, "b": {"c": [2, 1]}}))));
+
+        let jsonb = Jsonb::new(json!({"a": 1, "b": 2}));
+        assert!(jsonb.semantic_eq(&Jsonb::new(json!({"b": 2, "a": 1}))));
+        assert!(!jsonb.semantic_eq(&Jsonb::new(json!({"a": 1}))));
     }
 }
@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, ops::Deref};
 
 use diesel::{
     deserialize::{FromSql, FromSqlRow},
@@ -99,6 +99,32 @@ macro_rules! impl_nullable {
                 pub fn into_inner(self) -> Option<T> {
                     self.0
                 }
+
+                /// Returns a reference to the value, if any.
+                pub const fn as_ref(&self) -> Option<&T> {
+                    self.0.as_ref()
+                }
+
+                /// Returns a mutable reference to the value, if any.
+                pub const fn as_mut(&mut self) -> Option<&mut T> {
+                    self.0.as_mut()
+                }
+
+                /// Applies `f` to the value, if any, keeping the same wrapper.
+                pub fn map<U, F>(self, f: F) -> $type<U>
+                where
+                    F: FnOnce(T) -> U,
+                {
+                    $type(self.0.map(f))
+                }
+            }
+
+            impl<T> Deref for $type<T> {
+                type Target = Option<T>;
+
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
             }
 
             impl<T> From<$type<T>> for Option<T> {
@@ -123,7 +149,7 @@ macro_rules! impl_nullable {
 
                 fn from_nullable_sql(value: Option<PgValue>) -> diesel::deserialize::Result<Self> {
                     Ok(match value {
-                        Some(bytes) => Self::from_sql(bytes)?,
+                        Some(bytes) => <Self as FromSql<Nullable<$diesel_type_import>, Pg>>::from_sql(bytes)?,
                         None => Self(None),
                     })
                 }
@@ -146,6 +172,103 @@ macro_rules! impl_nullable {
 }
 
 impl_nullable!(
-    NullableJson => Json => JSON => crate::json::convert::sql_serialize => crate::json::convert::sql_deserialize => diesel::sql_types::Json,
-    NullableJsonb => Jsonb => JSONB => crate::json::convert::sql_serialize_binary => crate::json::convert::sql_deserialize_binary => diesel::pg::sql_types::Jsonb
+    NullableJson => Json => JSON => crate::json::convert::serialize_json => crate::json::convert::deserialize_json => diesel::sql_types::Json,
+    NullableJsonb => Jsonb => JSONB => crate::json::convert::serialize_jsonb => crate::json::convert::deserialize_jsonb => diesel::pg::sql_types::Jsonb
 );
+
+// MySQL has no `JSONB`, so only `NullableJson` is supported
+#[cfg(feature = "mysql")]
+impl<T> FromSql<Nullable<diesel::sql_types::Json>, diesel::mysql::Mysql> for NullableJson<T>
+where
+    T: DeserializeOwned,
+{
+    fn from_sql(value: diesel::mysql::MysqlValue<'_>) -> diesel::deserialize::Result<Self> {
+        crate::json::convert::deserialize_json_mysql(&value).map(Self)
+    }
+
+    fn from_nullable_sql(
+        value: Option<diesel::mysql::MysqlValue<'_>>,
+    ) -> diesel::deserialize::Result<Self> {
+        Ok(match value {
+            Some(bytes) => <Self as FromSql<
+                Nullable<diesel::sql_types::Json>,
+                diesel::mysql::Mysql,
+            >>::from_sql(bytes)?,
+            None => Self(None),
+        })
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl<T> ToSql<Nullable<diesel::sql_types::Json>, diesel::mysql::Mysql> for NullableJson<T>
+where
+    T: Debug + Serialize,
+{
+    fn to_sql(
+        &self,
+        out: &mut diesel::serialize::Output<diesel::mysql::Mysql>,
+    ) -> diesel::serialize::Result {
+        if let Some(value) = &self.0 {
+            crate::json::convert::serialize_json_mysql(value, out)
+        } else {
+            Ok(IsNull::Yes)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NullableJson, NullableJsonb};
+
+    #[test]
+    fn accessors() {
+        let mut json = NullableJson::new(Some(2));
+        assert_eq!(json.as_ref(), Some(&2));
+        *json.as_mut().unwrap() += 1;
+        assert_eq!(*json, Some(3));
+        assert!(json.is_some());
+        assert_eq!(
+            json.map(|n| n.to_string()).into_inner(),
+            Some("3".to_owned())
+        );
+
+        let mut jsonb = NullableJsonb::<i32>::new(None);
+        assert_eq!(jsonb.as_ref(), None);
+        assert_eq!(jsonb.as_mut(), None);
+        assert!(jsonb.is_none());
+        assert_eq!(jsonb.map(|n| n + 1), NullableJsonb::new(None));
+        assert_eq!(Option::from(NullableJsonb::from(Some(1))), Some(1));
+    }
+
+    #[cfg(feature = "mysql")]
+    #[test]
+    fn mysql_round_trip() {
+        use diesel::{
+            deserialize::FromSql,
+            mysql::{Mysql, MysqlType, MysqlValue},
+            query_builder::{BindCollector, bind_collector::RawBytesBindCollector},
+            sql_types::{Json, Nullable},
+        };
+
+        fn round_trip(json: &NullableJson<Vec<i32>>) -> NullableJson<Vec<i32>> {
+            let mut collector = RawBytesBindCollector::<Mysql>::new();
+            collector
+                .push_bound_value::<Nullable<Json>, _>(json, &mut ())
+                .unwrap();
+            let value = collector.binds[0]
+                .as_deref()
+                .map(|bytes| MysqlValue::new(bytes, MysqlType::String));
+            <NullableJson<Vec<i32>> as FromSql<Nullable<Json>, Mysql>>::from_nullable_sql(value)
+                .unwrap()
+        }
+
+        assert_eq!(
+            round_trip(&NullableJson::new(None)),
+            NullableJson::new(None)
+        );
+        assert_eq!(
+            round_trip(&NullableJson::new(Some(vec![1, 2]))),
+            NullableJson::new(Some(vec![1, 2]))
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use std::fmt::Debug;
+
+use diesel::{
+    deserialize::{FromSql, FromSqlRow},
+    expression::AsExpression,
+    pg::{Pg, PgValue},
+    serialize::ToSql,
+    sql_types,
+};
+use serde_core::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    error::UnknownJsonFields,
+    json::convert::{serialize_json, serialize_jsonb, sql_deserialize_binary_raw},
+};
+
+/// A diesel [`Json`] and [`Jsonb`] serialization and deserialization wrapper
+/// rejecting unknown fields
+///
+/// This type works like benzina [`Json`](crate::Json) and
+/// [`Jsonb`](crate::Jsonb), except that deserialization fails if the value
+/// contains any field that `T` would ignore, regardless of whether `T` uses
+/// `#[serde(deny_unknown_fields)]`. This helps catch drift between the
+/// stored documents and the Rust types.
+///
+/// ```
+/// use benzina::{StrictJson, U31, json_deserialize_as};
+/// use diesel::Queryable;
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Queryable)]
+/// #[diesel(table_name = users, check_for_backend(diesel::pg::Pg))]
+/// struct User {
+///     id: U31,
+///     #[diesel(deserialize_as = StrictJson<UserPermissions>)]
+///     permissions: UserPermissions,
+/// }
+///
+/// #[derive(Debug, Deserialize)]
+/// struct UserPermissions {
+///     can_delete: bool,
+///     can_update: bool,
+/// }
+///
+/// json_deserialize_as!(UserPermissions);
+///
+/// diesel::table! {
+///     users (id) {
+///         id -> Int4,
+///         permissions -> Jsonb,
+///     }
+/// }
+/// ```
+///
+/// [`Json`]: diesel::sql_types::Json
+/// [`Jsonb`]: diesel::sql_types::Jsonb
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, FromSqlRow, AsExpression,
+)]
+#[diesel(sql_type = sql_types::Json)]
+#[diesel(sql_type = sql_types::Jsonb)]
+pub struct StrictJson<T: Sized>(T);
+
+impl<T> StrictJson<T> {
+    pub const fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    pub fn get(&self) -> &T {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for StrictJson<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> AsRef<T> for StrictJson<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// Deserializes `bytes`, failing if any field was ignored
+fn deserialize_strict<T>(bytes: &[u8]) -> diesel::deserialize::Result<T>
+where
+    T: DeserializeOwned,
+{
+    let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+    let mut paths = Vec::new();
+    let value = serde_ignored::deserialize(&mut deserializer, |path| {
+        paths.push(path.to_string());
+    })?;
+    deserializer.end()?;
+
+    if paths.is_empty() {
+        Ok(value)
+    } else {
+        Err(UnknownJsonFields { paths }.into())
+    }
+}
+
+impl<T> FromSql<sql_types::Json, Pg> for StrictJson<T>
+where
+    T: DeserializeOwned,
+{
+    fn from_sql(value: PgValue<'_>) -> diesel::deserialize::Result<Self> {
+        deserialize_strict(value.as_bytes()).map(Self)
+    }
+}
+
+impl<T> FromSql<sql_types::Jsonb, Pg> for StrictJson<T>
+where
+    T: DeserializeOwned,
+{
+    fn from_sql(value: PgValue<'_>) -> diesel::deserialize::Result<Self> {
+        deserialize_strict(sql_deserialize_binary_raw(&value)?).map(Self)
+    }
+}
+
+impl<T> ToSql<sql_types::Json, Pg> for StrictJson<T>
+where
+    T: Debug + Serialize,
+{
+    fn to_sql(&self, out: &mut diesel::serialize::Output<Pg>) -> diesel::serialize::Result {
+        serialize_json(&self.0, out)
+    }
+}
+
+impl<T> ToSql<sql_types::Jsonb, Pg> for StrictJson<T>
+where
+    T: Debug + Serialize,
+{
+    fn to_sql(&self, out: &mut diesel::serialize::Output<Pg>) -> diesel::serialize::Result {
+        serialize_jsonb(&self.0, out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use crate::error::UnknownJsonFields;
+
+    use super::deserialize_strict;
+
+    #[derive(Debug, PartialEq, Eq, Deserialize)]
+    struct Permissions {
+        can_read: bool,
+        nested: Option<Nested>,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Deserialize)]
+    struct Nested {
+        level: u8,
+    }
+
+    #[test]
+    fn known_fields() {
+        assert_eq!(
+            deserialize_strict::<Permissions>(br#"{"can_read": true, "nested": {"level": 2}}"#)
+                .unwrap(),
+            Permissions {
+                can_read: true,
+                nested: Some(Nested { level: 2 }),
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        let err = deserialize_strict::<Permissions>(
+            br#"{"can_read": true, "can_fly": true, "nested": {"level": 2, "depth": 1}}"#,
+        )
+        .unwrap_err();
+        let err = err.downcast_ref::<UnknownJsonFields>().unwrap();
+        assert_eq!(err.paths(), ["can_fly", "nested.?.depth"]);
+        assert_eq!(
+            err.to_string(),
+            "unknown JSON fields: `can_fly`, `nested.?.depth`"
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(deserialize_strict::<Permissions>(br#"{"can_read": 1}"#).is_err());
+        assert!(deserialize_strict::<Permissions>(br#"{"can_read": true} {}"#).is_err());
+    }
+}
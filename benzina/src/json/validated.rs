@@ -0,0 +1,187 @@
+use std::fmt::Debug;
+
+use diesel::{
+    deserialize::{FromSql, FromSqlRow},
+    expression::AsExpression,
+    pg::{Pg, PgValue},
+    serialize::ToSql,
+    sql_types,
+};
+use serde_core::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    error::InvalidJson,
+    json::convert::{deserialize_json, deserialize_jsonb, serialize_json, serialize_jsonb},
+};
+
+/// The checks a value must pass to be read by [`ValidatedJson`]
+pub trait Validate {
+    /// Checks the invariants that can't be expressed through serde.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message describing why the value is invalid.
+    fn validate(&self) -> Result<(), String>;
+}
+
+/// A diesel [`Json`] and [`Jsonb`] serialization and deserialization wrapper
+/// validating the values it reads
+///
+/// This type works like benzina [`Json`](crate::Json) and
+/// [`Jsonb`](crate::Jsonb), except that [`Validate::validate`] is called
+/// after deserialization, failing with an [`InvalidJson`] error if the value
+/// doesn't pass it. Values are written without being validated.
+///
+/// ```
+/// use benzina::{U31, Validate, ValidatedJson, json_deserialize_as};
+/// use diesel::Queryable;
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Queryable)]
+/// #[diesel(table_name = users, check_for_backend(diesel::pg::Pg))]
+/// struct User {
+///     id: U31,
+///     #[diesel(deserialize_as = ValidatedJson<Roles>)]
+///     roles: Roles,
+/// }
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Roles(Vec<String>);
+///
+/// impl Validate for Roles {
+///     fn validate(&self) -> Result<(), String> {
+///         if self.0.is_empty() {
+///             Err("a user must have at least one role".to_owned())
+///         } else {
+///             Ok(())
+///         }
+///     }
+/// }
+///
+/// json_deserialize_as!(Roles);
+///
+/// diesel::table! {
+///     users (id) {
+///         id -> Int4,
+///         roles -> Jsonb,
+///     }
+/// }
+/// ```
+///
+/// [`Json`]: diesel::sql_types::Json
+/// [`Jsonb`]: diesel::sql_types::Jsonb
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, FromSqlRow, AsExpression,
+)]
+#[diesel(sql_type = sql_types::Json)]
+#[diesel(sql_type = sql_types::Jsonb)]
+pub struct ValidatedJson<T: Sized>(T);
+
+impl<T> ValidatedJson<T> {
+    pub const fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    pub fn get(&self) -> &T {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for ValidatedJson<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> AsRef<T> for ValidatedJson<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// Wraps `value` if it passes validation
+fn validated<T>(value: T) -> Result<ValidatedJson<T>, InvalidJson>
+where
+    T: Validate,
+{
+    match value.validate() {
+        Ok(()) => Ok(ValidatedJson(value)),
+        Err(message) => Err(InvalidJson { message }),
+    }
+}
+
+impl<T> FromSql<sql_types::Json, Pg> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+{
+    fn from_sql(value: PgValue<'_>) -> diesel::deserialize::Result<Self> {
+        validated(deserialize_json(value)?).map_err(Into::into)
+    }
+}
+
+impl<T> FromSql<sql_types::Jsonb, Pg> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+{
+    fn from_sql(value: PgValue<'_>) -> diesel::deserialize::Result<Self> {
+        validated(deserialize_jsonb(value)?).map_err(Into::into)
+    }
+}
+
+impl<T> ToSql<sql_types::Json, Pg> for ValidatedJson<T>
+where
+    T: Debug + Serialize,
+{
+    fn to_sql(&self, out: &mut diesel::serialize::Output<Pg>) -> diesel::serialize::Result {
+        serialize_json(&self.0, out)
+    }
+}
+
+impl<T> ToSql<sql_types::Jsonb, Pg> for ValidatedJson<T>
+where
+    T: Debug + Serialize,
+{
+    fn to_sql(&self, out: &mut diesel::serialize::Output<Pg>) -> diesel::serialize::Result {
+        serialize_jsonb(&self.0, out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Validate, validated};
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Percentage(u8);
+
+    impl Validate for Percentage {
+        fn validate(&self) -> Result<(), String> {
+            if self.0 <= 100 {
+                Ok(())
+            } else {
+                Err(format!("{} is greater than 100", self.0))
+            }
+        }
+    }
+
+    #[test]
+    fn valid() {
+        assert_eq!(
+            validated(Percentage(100)).unwrap().into_inner(),
+            Percentage(100)
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let err = validated(Percentage(101)).unwrap_err();
+        assert_eq!(err.message(), "101 is greater than 100");
+        assert_eq!(
+            err.to_string(),
+            "invalid JSON value: 101 is greater than 100"
+        );
+    }
+}
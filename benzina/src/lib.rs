@@ -1,39 +1,90 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 #[cfg(feature = "derive")]
-pub use benzina_derive::{Enum, join};
+pub use benzina_derive::{Enum, join, join_iter, join_map, join_sorted};
 
 #[cfg(feature = "array")]
-pub use self::array::{Array, ArrayWithNullableItems};
+pub use self::array::{Array, ArrayElement, ArrayWithNullableItems, BoundedVec, NonEmptyVec};
+#[cfg(feature = "bit-flags")]
+pub use self::bit_flags::BitFlags;
+#[cfg(feature = "bounded-string")]
+pub use self::bounded_string::BoundedString;
+#[cfg(feature = "citext")]
+pub use self::citext::CiText;
 #[cfg(feature = "ctid")]
-pub use self::ctid::{Ctid, ctid};
-pub use self::either::Either;
+pub use self::ctid::{Ctid, CtidCursor, ctid};
+pub use self::either::{Either, Either3, Either4, EitherFragment};
+#[cfg(feature = "hstore")]
+pub use self::hstore::HStore;
+#[cfg(feature = "network-types")]
+pub use self::inet::{Cidr, Inet};
 #[cfg(feature = "postgres")]
 pub use self::int::{U15, U31, U63};
+#[cfg(feature = "interval")]
+pub use self::interval::Interval;
 #[cfg(feature = "json")]
 pub use self::json::{
     Json,
     binary::Jsonb,
     nullable::{NullableJson, NullableJsonb},
+    strict::StrictJson,
+    validated::{Validate, ValidatedJson},
 };
+#[cfg(feature = "lsn")]
+pub use self::lsn::Lsn;
+#[cfg(feature = "ltree")]
+pub use self::ltree::{IsAncestorOf, IsDescendantOf, Ltree, LtreeExpressionMethods};
+#[cfg(feature = "network-types")]
+pub use self::macaddr::{MacAddr, MacAddr8};
+#[cfg(feature = "numeric")]
+pub use self::numeric::Decimal;
+#[cfg(feature = "range")]
+pub use self::range::PgRange;
 
 #[doc(hidden)]
 pub mod __private;
 #[cfg(feature = "array")]
 mod array;
+#[cfg(feature = "bit-flags")]
+mod bit_flags;
+#[cfg(feature = "bounded-string")]
+mod bounded_string;
+#[cfg(feature = "citext")]
+mod citext;
 #[cfg(feature = "ctid")]
 mod ctid;
 mod either;
-#[cfg(feature = "postgres")]
+#[cfg(any(feature = "derive", feature = "postgres", feature = "json"))]
 pub mod error;
 #[cfg(feature = "example-generated")]
 /// This module shows an example of code generated by the [`typed_uuid`] macro. IT **MUST NOT BE
 /// USED OUTSIDE THIS CRATE**.
 pub mod example_generated;
+#[cfg(feature = "derive")]
+mod group_children;
+#[cfg(feature = "hstore")]
+mod hstore;
+#[cfg(feature = "network-types")]
+mod inet;
 #[cfg(feature = "postgres")]
 mod int;
+#[cfg(feature = "interval")]
+mod interval;
 #[cfg(feature = "json")]
-mod json;
+pub mod json;
+#[cfg(feature = "lsn")]
+mod lsn;
+#[cfg(feature = "ltree")]
+mod ltree;
+#[cfg(feature = "network-types")]
+mod macaddr;
+#[cfg(feature = "numeric")]
+mod numeric;
+pub mod prelude;
+#[cfg(all(feature = "rand", feature = "postgres"))]
+mod rand;
+#[cfg(feature = "range")]
+mod range;
 #[cfg(all(feature = "schemars", feature = "postgres"))]
 mod schemars;
 #[cfg(all(feature = "serde", feature = "postgres"))]
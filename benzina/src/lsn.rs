@@ -0,0 +1,148 @@
+use std::{
+    fmt::{self, Display},
+    io::Write as _,
+    str::FromStr,
+};
+
+use diesel::{
+    deserialize::{self, FromSql, FromSqlRow},
+    expression::AsExpression,
+    pg::{Pg, PgValue},
+    serialize::{self, IsNull, Output, ToSql},
+};
+
+use crate::{error::ParseLsnError, sql_types::PgLsn};
+
+/// A PostgreSQL `pg_lsn` write-ahead log location
+///
+/// The textual representation is made of the upper and lower 32 bits of the
+/// location in hexadecimal, separated by a `/`. Locations are ordered like
+/// PostgreSQL orders them, so they can be compared to compute replication lag.
+///
+/// ```
+/// use benzina::Lsn;
+///
+/// let flushed: Lsn = "16/B374D848".parse().unwrap();
+/// let replayed = Lsn(0x16_B374_D000);
+/// assert!(replayed < flushed);
+/// assert_eq!(flushed.0 - replayed.0, 0x848);
+/// assert_eq!(replayed.to_string(), "16/B374D000");
+/// ```
+#[derive(
+    Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, AsExpression, FromSqlRow,
+)]
+#[diesel(sql_type = PgLsn)]
+pub struct Lsn(pub u64);
+
+impl Display for Lsn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:X}/{:X}", self.0 >> 32, self.0 & 0xFFFF_FFFF)
+    }
+}
+
+impl FromStr for Lsn {
+    type Err = ParseLsnError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_half = |half: &str| {
+            if half.is_empty() || half.len() > 8 || !half.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(ParseLsnError);
+            }
+            u64::from_str_radix(half, 16).map_err(|_| ParseLsnError)
+        };
+
+        let (upper, lower) = s.split_once('/').ok_or(ParseLsnError)?;
+        Ok(Self(parse_half(upper)? << 32 | parse_half(lower)?))
+    }
+}
+
+impl From<u64> for Lsn {
+    fn from(lsn: u64) -> Self {
+        Self(lsn)
+    }
+}
+
+impl From<Lsn> for u64 {
+    fn from(lsn: Lsn) -> Self {
+        lsn.0
+    }
+}
+
+impl FromSql<PgLsn, Pg> for Lsn {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let lsn =
+            <[u8; 8]>::try_from(bytes.as_bytes()).map_err(|_| "invalid log sequence number")?;
+        Ok(Self(u64::from_be_bytes(lsn)))
+    }
+}
+
+impl ToSql<PgLsn, Pg> for Lsn {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(&self.0.to_be_bytes())
+            .map(|()| IsNull::No)
+            .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Lsn;
+
+    #[test]
+    fn parse() {
+        assert_eq!("0/0".parse::<Lsn>().unwrap(), Lsn(0));
+        assert_eq!("16/B374D848".parse::<Lsn>().unwrap(), Lsn(0x16_B374_D848));
+        assert_eq!("FFFFFFFF/FFFFFFFF".parse::<Lsn>().unwrap(), Lsn(u64::MAX));
+        assert_eq!("a/b".parse::<Lsn>().unwrap(), Lsn(0xA_0000_000B));
+
+        for invalid in [
+            "",
+            "/",
+            "0/",
+            "/0",
+            "0",
+            "1/2/3",
+            "G/0",
+            "+1/0",
+            "100000000/0",
+        ] {
+            assert!(invalid.parse::<Lsn>().is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Lsn(0).to_string(), "0/0");
+        assert_eq!(Lsn(0x16_B374_D848).to_string(), "16/B374D848");
+        assert_eq!(Lsn(u64::MAX).to_string(), "FFFFFFFF/FFFFFFFF");
+        assert_eq!(Lsn(0x1_0000_0000).to_string(), "1/0");
+    }
+
+    #[test]
+    fn ordering() {
+        let lsns = ["0/1", "0/FFFFFFFF", "1/0", "1/10"].map(|lsn| lsn.parse::<Lsn>().unwrap());
+        assert!(lsns.is_sorted());
+    }
+
+    #[test]
+    fn from_sql_length() {
+        use std::num::NonZeroU32;
+
+        use diesel::{
+            deserialize::FromSql,
+            pg::{Pg, PgValue},
+        };
+
+        use crate::sql_types::PgLsn;
+
+        let oid = NonZeroU32::new(1).unwrap();
+        let from_sql =
+            |bytes: &[u8]| <Lsn as FromSql<PgLsn, Pg>>::from_sql(PgValue::new(bytes, &oid));
+        assert_eq!(
+            from_sql(&[0, 0, 0, 0x16, 0xB3, 0x74, 0xD8, 0x48]).unwrap(),
+            Lsn(0x16_B374_D848)
+        );
+        assert!(from_sql(&[0; 7]).is_err());
+        assert!(from_sql(&[0; 9]).is_err());
+    }
+}
@@ -0,0 +1,335 @@
+use std::{
+    fmt::{self, Display},
+    io::Write as _,
+    str::FromStr,
+};
+
+use diesel::{
+    deserialize::{self, FromSql, FromSqlRow},
+    expression::{AsExpression, Expression},
+    pg::{Pg, PgValue},
+    serialize::{self, IsNull, Output, ToSql},
+};
+
+use crate::{
+    error::InvalidLtree,
+    sql_types::{self, LtreeOrNullableLtree},
+};
+
+const VERSION: u8 = 1;
+const MAX_LABEL_LEN: usize = 1000;
+
+/// A PostgreSQL `ltree` label path
+///
+/// Labels are separated by `.` and may only contain ASCII letters, digits,
+/// `_` and `-`. The path with no labels is valid and represents the root.
+///
+/// ```
+/// use benzina::Ltree;
+///
+/// let mut path: Ltree = "Top.Science".parse().unwrap();
+/// path.push_label("Astronomy").unwrap();
+/// assert_eq!(path.as_str(), "Top.Science.Astronomy");
+///
+/// let parent = path.parent().unwrap();
+/// assert_eq!(parent.as_str(), "Top.Science");
+/// assert!(parent.is_ancestor_of(&path));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, FromSqlRow, AsExpression)]
+#[diesel(sql_type = sql_types::Ltree)]
+pub struct Ltree(String);
+
+impl Ltree {
+    /// Creates the empty path.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(String::new())
+    }
+
+    /// Creates a path from its labels.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the labels is invalid.
+    pub fn from_labels<I>(labels: I) -> Result<Self, InvalidLtree>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut path = Self::new();
+        for label in labels {
+            path.push_label(label.as_ref())?;
+        }
+        Ok(path)
+    }
+
+    /// Appends a label at the end of the path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the label is invalid, in which case the path is left untouched.
+    pub fn push_label(&mut self, label: &str) -> Result<(), InvalidLtree> {
+        validate_label(label)?;
+        if !self.0.is_empty() {
+            self.0.push('.');
+        }
+        self.0.push_str(label);
+        Ok(())
+    }
+
+    /// Returns the path without its last label, or `None` if the path is empty.
+    #[must_use]
+    pub fn parent(&self) -> Option<Self> {
+        if self.0.is_empty() {
+            return None;
+        }
+
+        let parent = self
+            .0
+            .rsplit_once('.')
+            .map_or("", |(parent, _label)| parent);
+        Some(Self(parent.to_owned()))
+    }
+
+    /// Returns `true` if `self` is an ancestor of `other` or equal to it,
+    /// like the `@>` operator.
+    #[must_use]
+    pub fn is_ancestor_of(&self, other: &Self) -> bool {
+        self.0.is_empty()
+            || other
+                .0
+                .strip_prefix(&self.0)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+    }
+
+    /// Returns an iterator over the labels of the path.
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        (!self.0.is_empty())
+            .then(|| self.0.split('.'))
+            .into_iter()
+            .flatten()
+    }
+
+    /// Returns the number of labels of the path.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.labels().count()
+    }
+
+    /// Returns `true` if the path has no labels.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the path in its textual representation.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+fn validate_label(label: &str) -> Result<(), InvalidLtree> {
+    if label.is_empty() {
+        return Err(InvalidLtree::EmptyLabel);
+    }
+    if label.len() > MAX_LABEL_LEN {
+        return Err(InvalidLtree::LabelTooLong);
+    }
+    if !label
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+    {
+        return Err(InvalidLtree::InvalidCharacter);
+    }
+    Ok(())
+}
+
+impl FromStr for Ltree {
+    type Err = InvalidLtree;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Ok(Self::new());
+        }
+
+        s.split('.').try_for_each(validate_label)?;
+        Ok(Self(s.to_owned()))
+    }
+}
+
+impl Display for Ltree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for Ltree {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<Ltree> for String {
+    fn from(path: Ltree) -> Self {
+        path.0
+    }
+}
+
+impl FromSql<sql_types::Ltree, Pg> for Ltree {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        match bytes.as_bytes().split_first() {
+            // the labels have already been validated by PostgreSQL, which may
+            // accept a broader set of characters depending on the locale
+            Some((&VERSION, path)) => Ok(Self(std::str::from_utf8(path)?.to_owned())),
+            _ => Err(InvalidLtree::UnsupportedVersion.into()),
+        }
+    }
+}
+
+impl ToSql<sql_types::Ltree, Pg> for Ltree {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(&[VERSION])?;
+        out.write_all(self.0.as_bytes())
+            .map(|()| IsNull::No)
+            .map_err(Into::into)
+    }
+}
+
+diesel::infix_operator!(IsAncestorOf, " @> ", backend: Pg);
+diesel::infix_operator!(IsDescendantOf, " <@ ", backend: Pg);
+
+/// `ltree` specific expression methods
+///
+/// ```
+/// use benzina::{Ltree, LtreeExpressionMethods};
+/// use diesel::{QueryDsl, pg::Pg};
+///
+/// let science: Ltree = "Top.Science".parse().unwrap();
+/// let query = categories::table
+///     .filter(categories::path.contained_by(science))
+///     .select(categories::id);
+/// assert_eq!(
+///     diesel::debug_query::<Pg, _>(&query).to_string(),
+///     r#"SELECT "categories"."id" FROM "categories" WHERE "categories"."path" <@ $1 -- binds: [Ltree("Top.Science")]"#
+/// );
+///
+/// diesel::table! {
+///     use diesel::sql_types::*;
+///     use benzina::sql_types::Ltree;
+///
+///     categories (id) {
+///         id -> Int4,
+///         path -> Ltree,
+///     }
+/// }
+/// ```
+pub trait LtreeExpressionMethods: Expression + Sized
+where
+    Self::SqlType: LtreeOrNullableLtree,
+{
+    /// Creates a PostgreSQL `@>` expression, checking whether `self` is an
+    /// ancestor of `other` or equal to it.
+    fn contains<T>(self, other: T) -> IsAncestorOf<Self, T::Expression>
+    where
+        T: AsExpression<Self::SqlType>,
+    {
+        IsAncestorOf::new(self, other.as_expression())
+    }
+
+    /// Creates a PostgreSQL `<@` expression, checking whether `self` is a
+    /// descendant of `other` or equal to it.
+    fn contained_by<T>(self, other: T) -> IsDescendantOf<Self, T::Expression>
+    where
+        T: AsExpression<Self::SqlType>,
+    {
+        IsDescendantOf::new(self, other.as_expression())
+    }
+}
+
+impl<T> LtreeExpressionMethods for T
+where
+    T: Expression,
+    T::SqlType: LtreeOrNullableLtree,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::InvalidLtree;
+
+    use super::Ltree;
+
+    #[test]
+    fn parse() {
+        let path: Ltree = "Top.Science.Astronomy".parse().unwrap();
+        assert_eq!(
+            path.labels().collect::<Vec<_>>(),
+            ["Top", "Science", "Astronomy"]
+        );
+        assert_eq!(path.len(), 3);
+        assert_eq!(path.to_string(), "Top.Science.Astronomy");
+
+        let empty: Ltree = "".parse().unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.len(), 0);
+        assert_eq!(empty, Ltree::new());
+
+        assert!(matches!(
+            "Top..Science".parse::<Ltree>(),
+            Err(InvalidLtree::EmptyLabel)
+        ));
+        assert!(matches!(
+            "Top.Science.".parse::<Ltree>(),
+            Err(InvalidLtree::EmptyLabel)
+        ));
+        assert!(matches!(
+            "Top.Sci ence".parse::<Ltree>(),
+            Err(InvalidLtree::InvalidCharacter)
+        ));
+        assert!(matches!(
+            "a".repeat(1001).parse::<Ltree>(),
+            Err(InvalidLtree::LabelTooLong)
+        ));
+        assert!("a_b-c.D1".parse::<Ltree>().is_ok());
+    }
+
+    #[test]
+    fn push_label() {
+        let mut path = Ltree::new();
+        path.push_label("Top").unwrap();
+        path.push_label("Science").unwrap();
+        assert_eq!(path.as_str(), "Top.Science");
+
+        assert!(path.push_label("Astro.nomy").is_err());
+        assert_eq!(path.as_str(), "Top.Science");
+
+        assert_eq!(Ltree::from_labels(["Top", "Science"]).unwrap(), path);
+        assert!(Ltree::from_labels(["Top", ""]).is_err());
+    }
+
+    #[test]
+    fn parent() {
+        let path: Ltree = "Top.Science.Astronomy".parse().unwrap();
+        let parent = path.parent().unwrap();
+        assert_eq!(parent.as_str(), "Top.Science");
+        let root = parent.parent().unwrap().parent().unwrap();
+        assert!(root.is_empty());
+        assert_eq!(root.parent(), None);
+    }
+
+    #[test]
+    fn is_ancestor_of() {
+        let top: Ltree = "Top".parse().unwrap();
+        let science: Ltree = "Top.Science".parse().unwrap();
+        let astronomy: Ltree = "Top.Science.Astronomy".parse().unwrap();
+        let scientist: Ltree = "Top.Scientist".parse().unwrap();
+
+        assert!(top.is_ancestor_of(&astronomy));
+        assert!(science.is_ancestor_of(&astronomy));
+        assert!(science.is_ancestor_of(&science));
+        assert!(Ltree::new().is_ancestor_of(&science));
+        assert!(!astronomy.is_ancestor_of(&science));
+        assert!(!science.is_ancestor_of(&scientist));
+    }
+}
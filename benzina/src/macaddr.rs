@@ -0,0 +1,144 @@
+use std::{
+    fmt::{self, Display},
+    io::Write as _,
+    str::FromStr,
+};
+
+use diesel::{
+    deserialize::{self, FromSql, FromSqlRow},
+    expression::AsExpression,
+    pg::{Pg, PgValue},
+    serialize::{self, IsNull, Output, ToSql},
+    sql_types,
+};
+
+use crate::error::ParseMacAddrError;
+
+/// A PostgreSQL `macaddr` 6 bytes MAC address
+///
+/// ```
+/// use benzina::MacAddr;
+///
+/// let addr: MacAddr = "08:00:2b:01:02:03".parse().unwrap();
+/// assert_eq!(addr, MacAddr([0x08, 0x00, 0x2b, 0x01, 0x02, 0x03]));
+/// assert_eq!(addr.to_string(), "08:00:2b:01:02:03");
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, AsExpression, FromSqlRow)]
+#[diesel(sql_type = sql_types::MacAddr)]
+pub struct MacAddr(pub [u8; 6]);
+
+/// A PostgreSQL `macaddr8` 8 bytes MAC address, in EUI-64 format
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, AsExpression, FromSqlRow)]
+#[diesel(sql_type = sql_types::MacAddr8)]
+pub struct MacAddr8(pub [u8; 8]);
+
+fn decode<const N: usize>(buf: &[u8]) -> deserialize::Result<[u8; N]> {
+    buf.try_into().map_err(|_| "invalid MAC address".into())
+}
+
+fn format_octets(octets: &[u8], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (i, octet) in octets.iter().enumerate() {
+        if i > 0 {
+            f.write_str(":")?;
+        }
+        write!(f, "{octet:02x}")?;
+    }
+    Ok(())
+}
+
+fn parse_octets<const N: usize>(s: &str) -> Result<[u8; N], ParseMacAddrError> {
+    let mut octets = [0; N];
+    let mut parts = s.split(':');
+    for octet in &mut octets {
+        let part = parts.next().ok_or(ParseMacAddrError)?;
+        if part.len() != 2 || !part.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(ParseMacAddrError);
+        }
+        *octet = u8::from_str_radix(part, 16).map_err(|_| ParseMacAddrError)?;
+    }
+
+    match parts.next() {
+        Some(_) => Err(ParseMacAddrError),
+        None => Ok(octets),
+    }
+}
+
+macro_rules! impl_macaddr {
+    ($($type:ident => $sql_type:ident),*) => {
+        $(
+            impl Display for $type {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    format_octets(&self.0, f)
+                }
+            }
+
+            impl FromStr for $type {
+                type Err = ParseMacAddrError;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    parse_octets(s).map(Self)
+                }
+            }
+
+            impl FromSql<sql_types::$sql_type, Pg> for $type {
+                fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+                    decode(bytes.as_bytes()).map(Self)
+                }
+            }
+
+            impl ToSql<sql_types::$sql_type, Pg> for $type {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+                    out.write_all(&self.0)
+                        .map(|()| IsNull::No)
+                        .map_err(Into::into)
+                }
+            }
+        )*
+    };
+}
+
+impl_macaddr! {
+    MacAddr => MacAddr,
+    MacAddr8 => MacAddr8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MacAddr, MacAddr8, decode};
+
+    #[test]
+    fn macaddr() {
+        let addr: MacAddr = "08:00:2B:01:02:03".parse().unwrap();
+        assert_eq!(addr.0, [0x08, 0x00, 0x2b, 0x01, 0x02, 0x03]);
+        assert_eq!(addr.to_string(), "08:00:2b:01:02:03");
+        assert_eq!(decode::<6>(&addr.0).unwrap(), addr.0);
+
+        for invalid in [
+            "",
+            "08:00:2b:01:02",
+            "08:00:2b:01:02:03:04",
+            "08:00:2b:01:02:3",
+            "08:00:2b:01:02:003",
+            "08-00-2b-01-02-03",
+            "08:00:2b:01:02:+3",
+            "08:00:2b:01:02:0g",
+        ] {
+            assert!(invalid.parse::<MacAddr>().is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn macaddr8() {
+        let addr: MacAddr8 = "08:00:2b:01:02:03:04:05".parse().unwrap();
+        assert_eq!(addr.0, [0x08, 0x00, 0x2b, 0x01, 0x02, 0x03, 0x04, 0x05]);
+        assert_eq!(addr.to_string(), "08:00:2b:01:02:03:04:05");
+        assert!("08:00:2b:01:02:03".parse::<MacAddr8>().is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(decode::<6>(&[]).is_err());
+        assert!(decode::<6>(&[0; 8]).is_err());
+        assert!(decode::<8>(&[0; 6]).is_err());
+    }
+}
@@ -0,0 +1,335 @@
+use std::{
+    fmt::{self, Display},
+    io::{self, Write},
+};
+
+use diesel::{
+    deserialize::{self, FromSql, FromSqlRow},
+    expression::AsExpression,
+    pg::{Pg, PgValue},
+    serialize::{self, IsNull, Output, ToSql},
+    sql_types::Numeric,
+};
+
+const NBASE: i128 = 10_000;
+const DEC_DIGITS: usize = 4;
+
+const NUMERIC_POS: u16 = 0x0000;
+const NUMERIC_NEG: u16 = 0x4000;
+
+/// An exact decimal number stored in a PostgreSQL `numeric` column
+///
+/// The value is represented as an integer `mantissa` scaled by `10^-scale`,
+/// i.e. `Decimal::from_minor_units(1234, 2)` is `12.34`. This allows representing
+/// monetary amounts without any loss of precision, as long as the mantissa fits in
+/// an [`i128`]. `NaN` and infinite values are rejected when reading from the database.
+///
+/// Equality compares the representation, so `1.0` and `1.00` are different values.
+///
+/// The [`Display`] implementation uses the scale of the value, unless a precision
+/// is specified, in which case the value is rounded half away from zero.
+///
+/// ```
+/// use benzina::Decimal;
+///
+/// let price = Decimal::from_minor_units(-1999, 2);
+/// assert_eq!(price.to_string(), "-19.99");
+/// assert_eq!(format!("{price:.1}"), "-20.0");
+/// assert_eq!(format!("{price:.4}"), "-19.9900");
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, AsExpression, FromSqlRow)]
+#[diesel(sql_type = Numeric)]
+pub struct Decimal {
+    mantissa: i128,
+    scale: u16,
+}
+
+impl Decimal {
+    /// Creates a decimal from an amount of minor units, i.e. cents with a
+    /// `scale` of `2`.
+    #[must_use]
+    pub const fn from_minor_units(mantissa: i128, scale: u16) -> Self {
+        Self { mantissa, scale }
+    }
+
+    /// Returns the value as an amount of minor units with the scale of the value.
+    #[must_use]
+    pub const fn mantissa(&self) -> i128 {
+        self.mantissa
+    }
+
+    /// Returns the number of digits after the decimal point.
+    #[must_use]
+    pub const fn scale(&self) -> u16 {
+        self.scale
+    }
+
+    /// Returns the value as an amount of minor units with the given scale, or `None`
+    /// if the conversion would lose precision or overflow.
+    #[must_use]
+    pub fn to_minor_units(&self, scale: u16) -> Option<i128> {
+        if scale >= self.scale {
+            self.mantissa
+                .checked_mul(10i128.checked_pow(u32::from(scale - self.scale))?)
+        } else {
+            let divisor = 10i128.checked_pow(u32::from(self.scale - scale))?;
+            (self.mantissa % divisor == 0).then(|| self.mantissa / divisor)
+        }
+    }
+}
+
+impl Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scale = usize::from(self.scale);
+        let precision = f.precision().unwrap_or(scale);
+
+        let mut digits = self.mantissa.unsigned_abs().to_string();
+        if digits.len() <= scale {
+            digits.insert_str(0, &"0".repeat(scale + 1 - digits.len()));
+        }
+        if precision < scale {
+            let len = digits.len() - (scale - precision);
+            let round_up = digits.as_bytes()[len] >= b'5';
+            digits.truncate(len);
+            if round_up {
+                digits = increment(&digits);
+            }
+        } else {
+            digits.push_str(&"0".repeat(precision - scale));
+        }
+
+        let is_nonnegative = self.mantissa >= 0 || digits.bytes().all(|d| d == b'0');
+        if precision > 0 {
+            digits.insert(digits.len() - precision, '.');
+        }
+        f.pad_integral(is_nonnegative, "", &digits)
+    }
+}
+
+/// Increments a string of ASCII digits by one
+fn increment(digits: &str) -> String {
+    let mut digits = digits.as_bytes().to_vec();
+    for digit in digits.iter_mut().rev() {
+        if *digit == b'9' {
+            *digit = b'0';
+        } else {
+            *digit += 1;
+            return String::from_utf8(digits).expect("digits are ASCII");
+        }
+    }
+    digits.insert(0, b'1');
+    String::from_utf8(digits).expect("digits are ASCII")
+}
+
+fn decode(buf: &[u8]) -> deserialize::Result<Decimal> {
+    let (&header, mut buf) = buf
+        .split_first_chunk::<8>()
+        .ok_or("invalid numeric header")?;
+    let ndigits = u16::from_be_bytes([header[0], header[1]]);
+    let weight = i16::from_be_bytes([header[2], header[3]]);
+    let sign = u16::from_be_bytes([header[4], header[5]]);
+    let scale = u16::from_be_bytes([header[6], header[7]]);
+
+    let negative = match sign {
+        NUMERIC_POS => false,
+        NUMERIC_NEG => true,
+        _ => return Err("NaN and infinite numeric values are not supported".into()),
+    };
+
+    let mut mantissa: i128 = 0;
+    for i in 0..ndigits {
+        let (&digit, rest) = buf
+            .split_first_chunk::<2>()
+            .ok_or("invalid numeric digit")?;
+        buf = rest;
+        let digit = i128::from(i16::from_be_bytes(digit));
+        if !(0..NBASE).contains(&digit) {
+            return Err("invalid numeric digit".into());
+        }
+        if digit == 0 {
+            continue;
+        }
+
+        // position of the least significant decimal digit of the group
+        let exponent = 4 * (i32::from(weight) - i32::from(i)) + i32::from(scale);
+        let term = if let Ok(exponent) = u32::try_from(exponent) {
+            10i128
+                .checked_pow(exponent)
+                .and_then(|pow| digit.checked_mul(pow))
+        } else {
+            // the digits beyond the scale of the value must be zero
+            let divisor = 10i128
+                .checked_pow(exponent.unsigned_abs())
+                .unwrap_or(i128::MAX);
+            (digit % divisor == 0).then(|| digit / divisor)
+        };
+        mantissa = term
+            .and_then(|term| mantissa.checked_add(term))
+            .ok_or("numeric value out of range")?;
+    }
+
+    Ok(Decimal {
+        mantissa: if negative { -mantissa } else { mantissa },
+        scale,
+    })
+}
+
+fn encode<W: Write>(decimal: &Decimal, out: &mut W) -> io::Result<()> {
+    let mut digits = decimal.mantissa.unsigned_abs().to_string();
+    let scale = usize::from(decimal.scale);
+    if digits.len() < scale {
+        digits.insert_str(0, &"0".repeat(scale - digits.len()));
+    }
+
+    // align the digits on the decimal point to groups of `DEC_DIGITS`
+    let int_len = digits.len() - scale;
+    let int_padding = (DEC_DIGITS - int_len % DEC_DIGITS) % DEC_DIGITS;
+    let frac_padding = (DEC_DIGITS - scale % DEC_DIGITS) % DEC_DIGITS;
+    let digits = format!(
+        "{}{digits}{}",
+        "0".repeat(int_padding),
+        "0".repeat(frac_padding)
+    );
+
+    let mut groups = digits
+        .as_bytes()
+        .chunks(DEC_DIGITS)
+        .map(|group| {
+            group
+                .iter()
+                .fold(0i16, |acc, &d| acc * 10 + i16::from(d - b'0'))
+        })
+        .collect::<Vec<_>>();
+    let mut weight =
+        i32::try_from((int_padding + int_len) / DEC_DIGITS).map_err(io::Error::other)? - 1;
+
+    let leading_zeros = groups.iter().take_while(|&&group| group == 0).count();
+    groups.drain(..leading_zeros);
+    weight -= i32::try_from(leading_zeros).map_err(io::Error::other)?;
+    while groups.last() == Some(&0) {
+        groups.pop();
+    }
+    if groups.is_empty() {
+        weight = 0;
+    }
+
+    let ndigits = u16::try_from(groups.len()).map_err(io::Error::other)?;
+    let weight = i16::try_from(weight).map_err(io::Error::other)?;
+    let sign = if decimal.mantissa < 0 {
+        NUMERIC_NEG
+    } else {
+        NUMERIC_POS
+    };
+
+    out.write_all(&ndigits.to_be_bytes())?;
+    out.write_all(&weight.to_be_bytes())?;
+    out.write_all(&sign.to_be_bytes())?;
+    out.write_all(&decimal.scale.to_be_bytes())?;
+    for group in groups {
+        out.write_all(&group.to_be_bytes())?;
+    }
+    Ok(())
+}
+
+impl FromSql<Numeric, Pg> for Decimal {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        decode(bytes.as_bytes())
+    }
+}
+
+impl ToSql<Numeric, Pg> for Decimal {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        encode(self, out).map(|()| IsNull::No).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Decimal, decode, encode};
+
+    fn round_trip(decimal: Decimal) -> Vec<u8> {
+        let mut buf = Vec::new();
+        encode(&decimal, &mut buf).unwrap();
+        assert_eq!(decode(&buf).unwrap(), decimal);
+        buf
+    }
+
+    #[test]
+    fn wire_format() {
+        // 12345.678 => digits [1, 2345, 6780], weight 1, scale 3
+        assert_eq!(
+            round_trip(Decimal::from_minor_units(12_345_678, 3)),
+            [0, 3, 0, 1, 0, 0, 0, 3, 0, 1, 0x09, 0x29, 0x1a, 0x7c]
+        );
+        // -0.05 => digits [500], weight -1, scale 2
+        assert_eq!(
+            round_trip(Decimal::from_minor_units(-5, 2)),
+            [0, 1, 0xff, 0xff, 0x40, 0, 0, 2, 0x01, 0xf4]
+        );
+        // 1000000 => digits [100], weight 1, scale 0
+        assert_eq!(
+            round_trip(Decimal::from_minor_units(1_000_000, 0)),
+            [0, 1, 0, 1, 0, 0, 0, 0, 0, 100]
+        );
+        // 0.00 => no digits
+        assert_eq!(
+            round_trip(Decimal::from_minor_units(0, 2)),
+            [0, 0, 0, 0, 0, 0, 0, 2]
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        for decimal in [
+            Decimal::from_minor_units(1, 0),
+            Decimal::from_minor_units(-1, 20),
+            Decimal::from_minor_units(123_456_789, 4),
+            Decimal::from_minor_units(-987_654_321_000, 7),
+            Decimal::from_minor_units(i128::MAX, 0),
+            Decimal::from_minor_units(i128::MAX, 38),
+            Decimal::from_minor_units(-i128::MAX, 5),
+        ] {
+            round_trip(decimal);
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(decode(&[]).is_err());
+        // NaN
+        assert!(decode(&[0, 0, 0, 0, 0xc0, 0, 0, 0]).is_err());
+        // missing digit
+        assert!(decode(&[0, 1, 0, 0, 0, 0, 0, 0]).is_err());
+        // digit out of range
+        assert!(decode(&[0, 1, 0, 0, 0, 0, 0, 0, 0x27, 0x10]).is_err());
+        // out of range of i128
+        assert!(decode(&[0, 1, 0, 10, 0, 0, 0, 0, 0, 1]).is_err());
+    }
+
+    #[test]
+    fn minor_units() {
+        let decimal = Decimal::from_minor_units(1250, 3);
+        assert_eq!(decimal.to_minor_units(2), Some(125));
+        assert_eq!(decimal.to_minor_units(5), Some(125_000));
+        assert_eq!(decimal.to_minor_units(1), None);
+        assert_eq!(
+            Decimal::from_minor_units(i128::MAX, 0).to_minor_units(1),
+            None
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Decimal::from_minor_units(1234, 2).to_string(), "12.34");
+        assert_eq!(Decimal::from_minor_units(-5, 3).to_string(), "-0.005");
+        assert_eq!(Decimal::from_minor_units(42, 0).to_string(), "42");
+        assert_eq!(format!("{:.2}", Decimal::from_minor_units(42, 0)), "42.00");
+        assert_eq!(format!("{:.0}", Decimal::from_minor_units(-25, 1)), "-3");
+        assert_eq!(format!("{:.1}", Decimal::from_minor_units(9999, 3)), "10.0");
+        assert_eq!(format!("{:.2}", Decimal::from_minor_units(-4, 3)), "0.00");
+        assert_eq!(
+            format!("{:>8.1}", Decimal::from_minor_units(1234, 2)),
+            "    12.3"
+        );
+    }
+}
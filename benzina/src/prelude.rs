@@ -0,0 +1,48 @@
+//! Re-exports the commonly used wrappers, traits and macros
+//!
+//! Only the items of the enabled features are re-exported, so that
+//! `use benzina::prelude::*;` imports exactly what is compiled.
+//!
+//! ```
+//! use benzina::prelude::*;
+//!
+//! let value: Either<u8, &str> = Either::Left(1);
+//! assert!(matches!(value, Either::Left(1)));
+//! ```
+
+#[cfg(feature = "bit-flags")]
+pub use crate::BitFlags;
+#[cfg(feature = "bounded-string")]
+pub use crate::BoundedString;
+#[cfg(feature = "citext")]
+pub use crate::CiText;
+#[cfg(feature = "numeric")]
+pub use crate::Decimal;
+#[cfg(feature = "derive")]
+pub use crate::Enum;
+#[cfg(feature = "hstore")]
+pub use crate::HStore;
+#[cfg(feature = "interval")]
+pub use crate::Interval;
+#[cfg(feature = "lsn")]
+pub use crate::Lsn;
+#[cfg(feature = "range")]
+pub use crate::PgRange;
+#[cfg(feature = "typed-uuid")]
+pub use crate::typed_uuid;
+#[cfg(feature = "array")]
+pub use crate::{
+    Array, ArrayElement, ArrayWithNullableItems, BoundedVec, NonEmptyVec, array_deserialize_as,
+};
+#[cfg(feature = "network-types")]
+pub use crate::{Cidr, Inet, MacAddr, MacAddr8};
+pub use crate::{Either, Either3, Either4, EitherFragment, either_chain};
+#[cfg(feature = "json")]
+pub use crate::{
+    Json, Jsonb, NullableJson, NullableJsonb, StrictJson, Validate, ValidatedJson,
+    json_deserialize_as,
+};
+#[cfg(feature = "ltree")]
+pub use crate::{Ltree, LtreeExpressionMethods};
+#[cfg(feature = "postgres")]
+pub use crate::{U15, U31, U63};
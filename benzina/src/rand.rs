@@ -0,0 +1,42 @@
+use rand::{
+    Rng,
+    distr::{Distribution, StandardUniform},
+};
+
+use crate::{U15, U31, U63};
+
+macro_rules! impl_rand_numbers {
+    ($($type:ident),*) => {
+        $(
+            impl Distribution<$type> for StandardUniform {
+                fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $type {
+                    $type::new(rng.random_range($type::MIN.get()..=$type::MAX.get()))
+                        .expect("the sampled value is in range")
+                }
+            }
+        )*
+    }
+}
+
+impl_rand_numbers! {
+    U15,
+    U31,
+    U63
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng as _, SeedableRng as _, rngs::SmallRng};
+
+    use crate::{U15, U31, U63};
+
+    #[test]
+    fn samples_in_range() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..10_000 {
+            assert!(rng.random::<U15>() <= U15::MAX);
+            assert!(rng.random::<U31>() <= U31::MAX);
+            assert!(rng.random::<U63>() <= U63::MAX);
+        }
+    }
+}
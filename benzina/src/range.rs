@@ -0,0 +1,343 @@
+use std::{
+    io::{self, Write},
+    ops::Bound,
+};
+
+use diesel::{
+    data_types::PgTimestamp,
+    deserialize::{self, FromSql, FromSqlRow},
+    expression::AsExpression,
+    pg::{Pg, PgValue},
+    serialize::{self, IsNull, Output, ToSql},
+    sql_types::{BigInt, Int4range, Int8range, Integer, Range, Timestamptz, Tstzrange},
+};
+
+const EMPTY: u8 = 0x01;
+const LB_INC: u8 = 0x02;
+const UB_INC: u8 = 0x04;
+const LB_INF: u8 = 0x08;
+const UB_INF: u8 = 0x10;
+
+/// A PostgreSQL range
+///
+/// Diesel maps range columns to a `(Bound<T>, Bound<T>)` tuple, which cannot
+/// represent the _empty_ range and is awkward to construct. This type carries
+/// the lower and upper bounds together with their inclusivity and supports
+/// `int4range`, `int8range` and `tstzrange` columns.
+///
+/// Keep in mind that PostgreSQL normalizes discrete ranges to the `[)` form,
+/// therefore `PgRange::closed(1, 5)` is read back as `PgRange::closed_open(1, 6)`.
+///
+/// ```
+/// use benzina::PgRange;
+/// use diesel::{Insertable, Queryable};
+///
+/// #[derive(Debug, Queryable)]
+/// #[diesel(table_name = reservations, check_for_backend(diesel::pg::Pg))]
+/// struct Reservation {
+///     id: i32,
+///     seats: PgRange<i32>,
+/// }
+///
+/// #[derive(Debug, Insertable)]
+/// #[diesel(table_name = reservations)]
+/// struct NewReservation {
+///     seats: PgRange<i32>,
+/// }
+///
+/// let new_reservation = NewReservation {
+///     seats: PgRange::closed_open(10, 20),
+/// };
+///
+/// diesel::table! {
+///     reservations (id) {
+///         id -> Int4,
+///         seats -> Int4range,
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, FromSqlRow, AsExpression)]
+#[diesel(sql_type = Int4range)]
+#[diesel(sql_type = Int8range)]
+#[diesel(sql_type = Tstzrange)]
+pub struct PgRange<T> {
+    bounds: Option<(Bound<T>, Bound<T>)>,
+}
+
+impl<T> PgRange<T> {
+    /// Creates an empty range.
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self { bounds: None }
+    }
+
+    /// Creates a range from arbitrary bounds.
+    #[must_use]
+    pub const fn new(lower: Bound<T>, upper: Bound<T>) -> Self {
+        Self {
+            bounds: Some((lower, upper)),
+        }
+    }
+
+    /// Creates the `[lower, upper)` range.
+    #[must_use]
+    pub const fn closed_open(lower: T, upper: T) -> Self {
+        Self::new(Bound::Included(lower), Bound::Excluded(upper))
+    }
+
+    /// Creates the `(lower, upper]` range.
+    #[must_use]
+    pub const fn open_closed(lower: T, upper: T) -> Self {
+        Self::new(Bound::Excluded(lower), Bound::Included(upper))
+    }
+
+    /// Creates the `[lower, upper]` range.
+    #[must_use]
+    pub const fn closed(lower: T, upper: T) -> Self {
+        Self::new(Bound::Included(lower), Bound::Included(upper))
+    }
+
+    /// Creates the `(lower, upper)` range.
+    #[must_use]
+    pub const fn open(lower: T, upper: T) -> Self {
+        Self::new(Bound::Excluded(lower), Bound::Excluded(upper))
+    }
+
+    /// Returns `true` if the range is empty.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.bounds.is_none()
+    }
+
+    /// Returns the lower bound, or `None` if the range is empty.
+    #[must_use]
+    pub fn lower(&self) -> Option<Bound<&T>> {
+        self.bounds.as_ref().map(|(lower, _upper)| lower.as_ref())
+    }
+
+    /// Returns the upper bound, or `None` if the range is empty.
+    #[must_use]
+    pub fn upper(&self) -> Option<Bound<&T>> {
+        self.bounds.as_ref().map(|(_lower, upper)| upper.as_ref())
+    }
+
+    /// Returns the lower and upper bounds, or `None` if the range is empty.
+    #[must_use]
+    pub fn into_bounds(self) -> Option<(Bound<T>, Bound<T>)> {
+        self.bounds
+    }
+}
+
+fn decode<T: RangeElement>(buf: &[u8]) -> deserialize::Result<PgRange<T>> {
+    let (&flags, mut buf) = buf.split_first().ok_or("invalid range flags")?;
+    if flags & EMPTY != 0 {
+        if !buf.is_empty() {
+            return Err("invalid range length".into());
+        }
+        return Ok(PgRange::empty());
+    }
+
+    let lower = if flags & LB_INF != 0 {
+        Bound::Unbounded
+    } else {
+        let (value, rest) = T::decode_bound(buf).ok_or("invalid range lower bound")?;
+        buf = rest;
+        if flags & LB_INC != 0 {
+            Bound::Included(value)
+        } else {
+            Bound::Excluded(value)
+        }
+    };
+
+    let upper = if flags & UB_INF != 0 {
+        Bound::Unbounded
+    } else {
+        let (value, rest) = T::decode_bound(buf).ok_or("invalid range upper bound")?;
+        buf = rest;
+        if flags & UB_INC != 0 {
+            Bound::Included(value)
+        } else {
+            Bound::Excluded(value)
+        }
+    };
+
+    if !buf.is_empty() {
+        return Err("invalid range length".into());
+    }
+    Ok(PgRange::new(lower, upper))
+}
+
+fn encode<T: RangeElement, W: Write>(range: &PgRange<T>, out: &mut W) -> io::Result<()> {
+    let Some((lower, upper)) = &range.bounds else {
+        return out.write_all(&[EMPTY]);
+    };
+
+    let flags = match lower {
+        Bound::Included(_) => LB_INC,
+        Bound::Excluded(_) => 0,
+        Bound::Unbounded => LB_INF,
+    } | match upper {
+        Bound::Included(_) => UB_INC,
+        Bound::Excluded(_) => 0,
+        Bound::Unbounded => UB_INF,
+    };
+    out.write_all(&[flags])?;
+
+    for bound in [lower, upper] {
+        if let Bound::Included(value) | Bound::Excluded(value) = bound {
+            value.encode_bound(out)?;
+        }
+    }
+    Ok(())
+}
+
+/// An element of a range with a fixed size binary representation
+trait RangeElement: Sized {
+    const SIZE: u32;
+
+    fn from_be_slice(buf: &[u8]) -> Option<Self>;
+
+    fn write_be<W: Write>(&self, out: &mut W) -> io::Result<()>;
+
+    fn decode_bound(buf: &[u8]) -> Option<(Self, &[u8])> {
+        let (&len, buf) = buf.split_first_chunk::<4>()?;
+        if u32::from_be_bytes(len) != Self::SIZE {
+            return None;
+        }
+        let (value, rest) = buf.split_at_checked(usize::try_from(Self::SIZE).ok()?)?;
+        Some((Self::from_be_slice(value)?, rest))
+    }
+
+    fn encode_bound<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        out.write_all(&Self::SIZE.to_be_bytes())?;
+        self.write_be(out)
+    }
+}
+
+impl RangeElement for i32 {
+    const SIZE: u32 = 4;
+
+    fn from_be_slice(buf: &[u8]) -> Option<Self> {
+        buf.try_into().ok().map(Self::from_be_bytes)
+    }
+
+    fn write_be<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        out.write_all(&self.to_be_bytes())
+    }
+}
+
+impl RangeElement for i64 {
+    const SIZE: u32 = 8;
+
+    fn from_be_slice(buf: &[u8]) -> Option<Self> {
+        buf.try_into().ok().map(Self::from_be_bytes)
+    }
+
+    fn write_be<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        out.write_all(&self.to_be_bytes())
+    }
+}
+
+impl RangeElement for PgTimestamp {
+    const SIZE: u32 = i64::SIZE;
+
+    fn from_be_slice(buf: &[u8]) -> Option<Self> {
+        i64::from_be_slice(buf).map(Self)
+    }
+
+    fn write_be<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        self.0.write_be(out)
+    }
+}
+
+macro_rules! impl_range {
+    ($($rust_type:ident => $diesel_type:ident),*) => {
+        $(
+            impl FromSql<Range<$diesel_type>, Pg> for PgRange<$rust_type> {
+                fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+                    decode(bytes.as_bytes())
+                }
+            }
+
+            impl ToSql<Range<$diesel_type>, Pg> for PgRange<$rust_type> {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+                    encode(self, out).map(|()| IsNull::No).map_err(Into::into)
+                }
+            }
+        )*
+    };
+}
+
+impl_range! {
+    i32 => Integer,
+    i64 => BigInt,
+    PgTimestamp => Timestamptz
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Bound;
+
+    use diesel::data_types::PgTimestamp;
+
+    use super::{PgRange, decode, encode};
+
+    #[test]
+    fn int4range() {
+        let range = PgRange::closed_open(1i32, 10);
+        let mut buf = Vec::new();
+        encode(&range, &mut buf).unwrap();
+        assert_eq!(buf, [0x02, 0, 0, 0, 4, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 10]);
+        assert_eq!(decode::<i32>(&buf).unwrap(), range);
+    }
+
+    #[test]
+    fn int8range() {
+        for range in [
+            PgRange::closed_open(-5i64, 5),
+            PgRange::open_closed(-5, 5),
+            PgRange::closed(-5, 5),
+            PgRange::open(-5, 5),
+            PgRange::new(Bound::Unbounded, Bound::Excluded(5)),
+            PgRange::new(Bound::Included(-5), Bound::Unbounded),
+            PgRange::new(Bound::Unbounded, Bound::Unbounded),
+        ] {
+            let mut buf = Vec::new();
+            encode(&range, &mut buf).unwrap();
+            assert_eq!(decode::<i64>(&buf).unwrap(), range);
+        }
+    }
+
+    #[test]
+    fn tstzrange() {
+        let range = PgRange::closed_open(PgTimestamp(0), PgTimestamp(86_400_000_000));
+        let mut buf = Vec::new();
+        encode(&range, &mut buf).unwrap();
+        assert_eq!(decode::<PgTimestamp>(&buf).unwrap(), range);
+    }
+
+    #[test]
+    fn empty() {
+        let range = PgRange::<i32>::empty();
+        let mut buf = Vec::new();
+        encode(&range, &mut buf).unwrap();
+        assert_eq!(buf, [0x01]);
+
+        let decoded = decode::<i32>(&buf).unwrap();
+        assert!(decoded.is_empty());
+        assert_eq!(decoded.lower(), None);
+        assert_eq!(decoded.upper(), None);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(decode::<i32>(&[]).is_err());
+        assert!(decode::<i32>(&[0x02, 0, 0, 0, 4, 0, 0]).is_err());
+        assert!(decode::<i32>(&[0x18, 0, 0, 0, 8]).is_err());
+        assert!(decode::<i32>(&[0x01, 0]).is_err());
+        assert!(
+            decode::<i32>(&[0x02, 0, 0, 0, 4, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 10, 0]).is_err()
+        );
+        assert!(decode::<i64>(&[0x12, 0, 0, 0, 4, 0, 0, 0, 1]).is_err());
+    }
+}
@@ -1,11 +1,16 @@
 use std::fmt;
+#[cfg(feature = "array")]
+use std::marker::PhantomData;
 
 use serde_core::{
     Deserialize, Deserializer, Serialize, Serializer,
-    de::{self, Unexpected, Visitor},
+    de::{self, MapAccess, SeqAccess, Unexpected, Visitor},
+    ser::SerializeStruct,
 };
 
-use crate::{U15, U31, U63};
+#[cfg(feature = "array")]
+use crate::{Array, ArrayWithNullableItems};
+use crate::{U15, U31, U63, sql_types::TidValue};
 
 macro_rules! impl_serde_numbers_visit {
     ($type:ident = [$($visit_fn:ident => $kind:ident($inner:ident) => $new_fn:ident),*]) => {
@@ -69,6 +74,163 @@ impl_serde_numbers! {
     U63 => u64, deserialize_u64
 }
 
+const TID_FIELDS: &[&str] = &["block_number", "offset_number"];
+
+/// Serializes as a `{ "block_number": .., "offset_number": .. }` struct,
+/// instead of the `(block,offset)` textual form, so that both numbers
+/// round-trip without any parsing.
+impl Serialize for TidValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tid = serializer.serialize_struct("TidValue", TID_FIELDS.len())?;
+        tid.serialize_field("block_number", &self.block_number)?;
+        tid.serialize_field("offset_number", &self.offset_number)?;
+        tid.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for TidValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        enum Field {
+            BlockNumber,
+            OffsetNumber,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct FieldVisitor;
+
+                impl Visitor<'_> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                        f.write_str("`block_number` or `offset_number`")
+                    }
+
+                    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                        match v {
+                            "block_number" => Ok(Field::BlockNumber),
+                            "offset_number" => Ok(Field::OffsetNumber),
+                            _ => Err(de::Error::unknown_field(v, TID_FIELDS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        struct TidVisitor;
+
+        impl<'de> Visitor<'de> for TidVisitor {
+            type Value = TidValue;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("struct TidValue")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let block_number = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let offset_number = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                Ok(TidValue {
+                    block_number,
+                    offset_number,
+                })
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut block_number = None;
+                let mut offset_number = None;
+                while let Some(field) = map.next_key()? {
+                    match field {
+                        Field::BlockNumber if block_number.is_some() => {
+                            return Err(de::Error::duplicate_field("block_number"));
+                        }
+                        Field::BlockNumber => block_number = Some(map.next_value()?),
+                        Field::OffsetNumber if offset_number.is_some() => {
+                            return Err(de::Error::duplicate_field("offset_number"));
+                        }
+                        Field::OffsetNumber => offset_number = Some(map.next_value()?),
+                    }
+                }
+                Ok(TidValue {
+                    block_number: block_number
+                        .ok_or_else(|| de::Error::missing_field("block_number"))?,
+                    offset_number: offset_number
+                        .ok_or_else(|| de::Error::missing_field("offset_number"))?,
+                })
+            }
+        }
+
+        deserializer.deserialize_struct("TidValue", TID_FIELDS, TidVisitor)
+    }
+}
+
+/// Deserializes a sequence of exactly `N` items
+#[cfg(feature = "array")]
+struct ArrayVisitor<T, const N: usize>(PhantomData<T>);
+
+#[cfg(feature = "array")]
+impl<'de, T: Deserialize<'de>, const N: usize> Visitor<'de> for ArrayVisitor<T, N> {
+    type Value = [T; N];
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an array of length {N}")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(N));
+        while items.len() < N {
+            let Some(item) = seq.next_element()? else {
+                return Err(de::Error::invalid_length(items.len(), &self));
+            };
+            items.push(item);
+        }
+        // Fail on the first extra item instead of collecting all of them
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(N + 1, &self));
+        }
+        items
+            .try_into()
+            .map_err(|items: Vec<T>| de::Error::invalid_length(items.len(), &self))
+    }
+}
+
+#[cfg(feature = "array")]
+impl<T: Serialize, const N: usize> Serialize for Array<T, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.as_slice())
+    }
+}
+
+#[cfg(feature = "array")]
+impl<'de, T: Deserialize<'de>, const N: usize> Deserialize<'de> for Array<T, N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_seq(ArrayVisitor(PhantomData))
+            .map(Self::new)
+    }
+}
+
+#[cfg(feature = "array")]
+impl<T: Serialize, const N: usize> Serialize for ArrayWithNullableItems<T, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.as_slice())
+    }
+}
+
+#[cfg(feature = "array")]
+impl<'de, T: Deserialize<'de>, const N: usize> Deserialize<'de> for ArrayWithNullableItems<T, N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_seq(ArrayVisitor(PhantomData))
+            .map(Self::new)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_test::{Token, assert_de_tokens, assert_ser_tokens};
@@ -142,4 +304,93 @@ mod tests {
         U63, u64, I32, i32, int_de_u63_from_i32,
         U63, u64, I64, i64, int_de_u63_from_i64
     }
+
+    #[cfg(feature = "array")]
+    #[test]
+    fn array() {
+        use serde_test::{assert_de_tokens_error, assert_tokens};
+
+        use crate::Array;
+
+        assert_tokens(
+            &Array::new([1, 2]),
+            &[
+                Token::Seq { len: Some(2) },
+                Token::I32(1),
+                Token::I32(2),
+                Token::SeqEnd,
+            ],
+        );
+        assert_de_tokens_error::<Array<i32, 2>>(
+            &[Token::Seq { len: Some(1) }, Token::I32(1), Token::SeqEnd],
+            "invalid length 1, expected an array of length 2",
+        );
+        assert_de_tokens_error::<Array<i32, 2>>(
+            &[
+                Token::Seq { len: Some(3) },
+                Token::I32(1),
+                Token::I32(2),
+                Token::I32(3),
+                Token::SeqEnd,
+            ],
+            "invalid length 3, expected an array of length 2",
+        );
+        // The items after the first extra one are never read
+        let err = serde_json::from_str::<Array<i32, 2>>(r#"[1, 2, 3, "four"]"#).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("invalid length 3, expected an array of length 2"),
+            "{err}"
+        );
+    }
+
+    #[cfg(feature = "array")]
+    #[test]
+    fn array_with_nullable_items() {
+        use serde_test::{assert_de_tokens_error, assert_tokens};
+
+        use crate::ArrayWithNullableItems;
+
+        assert_tokens(
+            &ArrayWithNullableItems::new([Some(1), None]),
+            &[
+                Token::Seq { len: Some(2) },
+                Token::Some,
+                Token::I32(1),
+                Token::None,
+                Token::SeqEnd,
+            ],
+        );
+        assert_de_tokens_error::<ArrayWithNullableItems<i32, 2>>(
+            &[Token::Seq { len: Some(1) }, Token::None, Token::SeqEnd],
+            "invalid length 1, expected an array of length 2",
+        );
+    }
+
+    #[test]
+    fn tid_value() {
+        use crate::sql_types::TidValue;
+
+        let tid = TidValue {
+            block_number: 42,
+            offset_number: 7,
+        };
+        let json = serde_json::to_string(&tid).unwrap();
+        assert_eq!(json, r#"{"block_number":42,"offset_number":7}"#);
+        assert_eq!(serde_json::from_str::<TidValue>(&json).unwrap(), tid);
+        assert_eq!(serde_json::from_str::<TidValue>("[42,7]").unwrap(), tid);
+
+        for invalid in [
+            r#"{"block_number":42}"#,
+            r#"{"block_number":42,"offset_number":7,"page":1}"#,
+            r#"{"block_number":42,"block_number":1,"offset_number":7}"#,
+            r#"{"block_number":42,"offset_number":70000}"#,
+            "[42]",
+        ] {
+            assert!(
+                serde_json::from_str::<TidValue>(invalid).is_err(),
+                "{invalid}"
+            );
+        }
+    }
 }
@@ -0,0 +1,8 @@
+use diesel::{query_builder::QueryId, sql_types::SqlType};
+
+/// The `citext` type of the PostgreSQL extension with the same name
+///
+/// Since extension types don't have a fixed OID, it is looked up by name.
+#[derive(Debug, Copy, Clone, Default, QueryId, SqlType)]
+#[diesel(postgres_type(name = "citext"))]
+pub struct CiText;
@@ -0,0 +1,17 @@
+use diesel::{
+    query_builder::QueryId,
+    sql_types::{Nullable, SqlType},
+};
+
+/// The `ltree` type of the PostgreSQL extension with the same name
+///
+/// Since extension types don't have a fixed OID, it is looked up by name.
+#[derive(Debug, Copy, Clone, Default, QueryId, SqlType)]
+#[diesel(postgres_type(name = "ltree"))]
+pub struct Ltree;
+
+/// Marker trait for the SQL types accepted by [`LtreeExpressionMethods`](crate::LtreeExpressionMethods)
+pub trait LtreeOrNullableLtree: SqlType {}
+
+impl LtreeOrNullableLtree for Ltree {}
+impl LtreeOrNullableLtree for Nullable<Ltree> {}
@@ -1,3 +1,19 @@
+#[cfg(feature = "citext")]
+pub use self::citext::CiText;
+#[cfg(feature = "hstore")]
+pub use self::hstore::HStore;
+#[cfg(feature = "ltree")]
+pub use self::ltree::{Ltree, LtreeOrNullableLtree};
+#[cfg(feature = "lsn")]
+pub use self::pg_lsn::PgLsn;
 pub use self::tid::{Tid, TidValue};
 
+#[cfg(feature = "citext")]
+mod citext;
+#[cfg(feature = "hstore")]
+mod hstore;
+#[cfg(feature = "ltree")]
+mod ltree;
+#[cfg(feature = "lsn")]
+mod pg_lsn;
 mod tid;
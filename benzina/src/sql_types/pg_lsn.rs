@@ -0,0 +1,6 @@
+use diesel::{query_builder::QueryId, sql_types::SqlType};
+
+/// The PostgreSQL `pg_lsn` type
+#[derive(Debug, Copy, Clone, Default, QueryId, SqlType)]
+#[diesel(postgres_type(oid = 3220, array_oid = 3221))]
+pub struct PgLsn;
@@ -1,4 +1,4 @@
-use std::io::Write as _;
+use std::{cmp::Ordering, io::Write as _};
 
 use diesel::{
     deserialize::{self, FromSql, FromSqlRow},
@@ -13,13 +13,63 @@ use diesel::{
 #[diesel(postgres_type(oid = 27, array_oid = 1010))]
 pub struct Tid;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, AsExpression, FromSqlRow)]
+/// A PostgreSQL `tid` tuple identifier
+///
+/// Tuple identifiers are ordered by block number first and offset number
+/// second, like PostgreSQL orders them. They can also be compared against
+/// a raw `(block_number, offset_number)` pair.
+///
+/// With the `serde` feature, it is (de)serialized as a struct with the
+/// `block_number` and `offset_number` fields.
+///
+/// ```
+/// use benzina::sql_types::TidValue;
+///
+/// let tid = TidValue {
+///     block_number: 1,
+///     offset_number: 4,
+/// };
+/// assert_eq!(tid, (1, 4));
+/// assert!(tid < (2, 0));
+/// assert!((1, 3) < tid);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, AsExpression, FromSqlRow)]
 #[diesel(sql_type = Tid)]
 pub struct TidValue {
     pub block_number: u32,
     pub offset_number: u16,
 }
 
+impl TidValue {
+    const fn as_pair(self) -> (u32, u16) {
+        (self.block_number, self.offset_number)
+    }
+}
+
+impl PartialEq<(u32, u16)> for TidValue {
+    fn eq(&self, other: &(u32, u16)) -> bool {
+        self.as_pair() == *other
+    }
+}
+
+impl PartialEq<TidValue> for (u32, u16) {
+    fn eq(&self, other: &TidValue) -> bool {
+        *self == other.as_pair()
+    }
+}
+
+impl PartialOrd<(u32, u16)> for TidValue {
+    fn partial_cmp(&self, other: &(u32, u16)) -> Option<Ordering> {
+        self.as_pair().partial_cmp(other)
+    }
+}
+
+impl PartialOrd<TidValue> for (u32, u16) {
+    fn partial_cmp(&self, other: &TidValue) -> Option<Ordering> {
+        self.partial_cmp(&other.as_pair())
+    }
+}
+
 impl FromSql<Tid, Pg> for TidValue {
     fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
         let buf = bytes.as_bytes();
@@ -42,3 +92,31 @@ impl ToSql<Tid, Pg> for TidValue {
         out.write_all(&buf).map(|()| IsNull::No).map_err(Into::into)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::TidValue;
+
+    #[test]
+    fn compare_with_pair() {
+        let tid = TidValue {
+            block_number: 7,
+            offset_number: 3,
+        };
+        assert_eq!(tid, (7, 3));
+        assert_eq!((7, 3), tid);
+        assert_ne!(tid, (3, 7));
+
+        assert!(tid < (7, 4));
+        assert!(tid < (8, 0));
+        assert!(tid > (6, u16::MAX));
+        assert!((7, 2) < tid);
+        assert!((7, 3) <= tid);
+
+        let next = TidValue {
+            block_number: 8,
+            offset_number: 0,
+        };
+        assert!(tid < next);
+    }
+}
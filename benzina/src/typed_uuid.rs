@@ -19,6 +19,7 @@
 )]
 ///
 /// [`Uuid`]: ::uuid::Uuid
+/// [`Uuid::from_slice`]: ::uuid::Uuid::from_slice
 ///
 /// # Examples
 ///
@@ -47,6 +48,23 @@
 /// }
 /// ```
 ///
+/// The generated types implement [`Borrow<Uuid>`](std::borrow::Borrow), so maps keyed by them can
+/// be queried with a plain `Uuid`. They also implement `AsRef<[u8]>` to access the bytes, but not
+/// `Borrow<[u8]>`, because a `Uuid` doesn't hash like its bytes: convert the bytes with
+/// [`Uuid::from_slice`] to query the map instead.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use uuid::Uuid;
+///
+/// benzina::typed_uuid!(FooId);
+///
+/// fn lookup<'a>(names: &'a HashMap<FooId, String>, bytes: &[u8]) -> Option<&'a String> {
+///     names.get(&Uuid::from_slice(bytes).ok()?)
+/// }
+/// ```
+///
 /// Keep in mind that there is no way[^See note] to construct an instance unless the instantiation is done
 /// inside the module containing the new type or a submodule of it:
 ///
@@ -300,6 +318,8 @@ macro_rules! typed_uuid {
                 }
             }
 
+            // There is no matching `Borrow<[u8]>`, since the `Hash` of `Uuid` differs from the one
+            // of its bytes
             impl $crate::__private::std::convert::AsRef<[u8]> for $name {
                 fn as_ref(&self) -> &[u8] {
                     $crate::__private::std::convert::AsRef::as_ref(&self.0)
@@ -312,6 +332,8 @@ macro_rules! typed_uuid {
                 }
             }
 
+            // `Borrow` requires the hashes to match, which holds because the derived `Hash` only
+            // hashes the inner `Uuid`
             impl $crate::__private::std::borrow::Borrow<$crate::__private::uuid::Uuid> for $name {
                 fn borrow(&self) -> &$crate::__private::uuid::Uuid {
                     &self.0
@@ -379,9 +401,23 @@ macro_rules! __typed_uuid__impl_dangerous_construction {
         /// Creates a new typed `Uuid` which does not come from the database.
         #[must_use]
         #[allow(unused)]
-        $vis fn dangerous_new(inner: $crate::__private::uuid::Uuid) -> Self {
+        $vis const fn dangerous_new(inner: $crate::__private::uuid::Uuid) -> Self {
             Self(inner)
         }
+
+        /// Creates a new typed `Uuid` from raw bytes which do not come from the database.
+        ///
+        /// The bytes are parsed the same way as when reading from the database.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if `bytes` is not exactly 16 bytes long.
+        #[allow(unused)]
+        $vis fn try_from_bytes(
+            bytes: &[u8],
+        ) -> $crate::__private::std::result::Result<Self, $crate::__private::uuid::Error> {
+            $crate::__private::uuid::Uuid::from_slice(bytes).map(Self)
+        }
     };
 }
 
@@ -430,6 +466,47 @@ mod test {
         assert_eq!(new.get(), inner);
     }
 
+    #[cfg(feature = "dangerous-construction")]
+    #[test]
+    fn const_construction() {
+        crate::typed_uuid!(Foo);
+        const FOO: Foo =
+            Foo::dangerous_new(Uuid::from_u128(0x6c2c_49f0_8d4e_4b27_9b1a_0d3e_5f7a_2c11));
+
+        assert_eq!(
+            FOO.get(),
+            Uuid::from_u128(0x6c2c_49f0_8d4e_4b27_9b1a_0d3e_5f7a_2c11)
+        );
+    }
+
+    #[cfg(feature = "dangerous-construction")]
+    #[test]
+    fn borrowed_lookup() {
+        use std::{collections::HashMap, hash::BuildHasher as _};
+
+        crate::typed_uuid!(Foo);
+        let inner = Uuid::new_v4();
+        let foo = Foo::dangerous_new(inner);
+
+        let map = HashMap::from([(foo, "foo")]);
+        assert_eq!(map.get(&inner), Some(&"foo"));
+        assert_eq!(map.hasher().hash_one(foo), map.hasher().hash_one(inner));
+    }
+
+    #[cfg(feature = "dangerous-construction")]
+    #[test]
+    fn try_from_bytes() {
+        crate::typed_uuid!(Foo);
+        let inner = Uuid::new_v4();
+
+        let foo = Foo::try_from_bytes(inner.as_bytes()).unwrap();
+        assert_eq!(foo.get(), inner);
+
+        assert!(Foo::try_from_bytes(&[]).is_err());
+        assert!(Foo::try_from_bytes(&inner.as_bytes()[..15]).is_err());
+        assert!(Foo::try_from_bytes(&[0; 17]).is_err());
+    }
+
     #[test]
     fn partial_ord() {
         crate::typed_uuid!(Foo);
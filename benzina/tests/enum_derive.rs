@@ -0,0 +1,741 @@
+#![cfg(feature = "derive")]
+
+pub mod schema {
+    pub mod sql_types {
+        #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+        #[cfg_attr(feature = "postgres", diesel(postgres_type(name = "animal")))]
+        #[cfg_attr(feature = "mysql", diesel(mysql_type(name = "Enum")))]
+        pub struct Animal;
+
+        #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+        #[cfg_attr(feature = "postgres", diesel(postgres_type(name = "farm_animal")))]
+        pub struct FarmAnimal;
+    }
+
+    diesel::table! {
+        use diesel::sql_types::*;
+        use super::sql_types::FarmAnimal;
+
+        farm (id) {
+            id -> Int4,
+            animal -> FarmAnimal,
+        }
+    }
+}
+
+mod ordered {
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, benzina::Enum)]
+    #[benzina(sql_type = crate::schema::sql_types::Animal, ordered)]
+    enum Animal {
+        Rabbit,
+        Duck,
+        Goose,
+        Chicken,
+    }
+
+    #[test]
+    fn declaration_order() {
+        assert!(Animal::Rabbit < Animal::Duck);
+        assert!(Animal::Goose > Animal::Duck);
+        assert_eq!(Animal::Chicken.max(Animal::Rabbit), Animal::Chicken);
+
+        let mut animals = [Animal::Chicken, Animal::Goose, Animal::Rabbit, Animal::Duck];
+        animals.sort();
+        assert_eq!(
+            animals,
+            [Animal::Rabbit, Animal::Duck, Animal::Goose, Animal::Chicken]
+        );
+        assert_eq!(animals.map(|animal| animal.as_index()), [0, 1, 2, 3]);
+    }
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, benzina::Enum)]
+    #[benzina(sql_type = crate::schema::sql_types::Animal, ordered)]
+    #[repr(i32)]
+    enum NumberedAnimal {
+        Rabbit = 5,
+        Duck = 2,
+        Goose,
+    }
+
+    #[test]
+    fn explicit_discriminants() {
+        assert_eq!(NumberedAnimal::Rabbit as i32, 5);
+        assert_eq!(NumberedAnimal::Duck as i32, 2);
+        assert_eq!(NumberedAnimal::Goose as i32, 3);
+
+        // the ordering still follows the declaration, like PostgreSQL does
+        assert!(NumberedAnimal::Rabbit < NumberedAnimal::Duck);
+        assert_eq!(NumberedAnimal::Goose.as_index(), 2);
+    }
+}
+
+mod iter {
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, benzina::Enum)]
+    #[benzina(sql_type = crate::schema::sql_types::Animal, iter)]
+    enum Animal {
+        Rabbit,
+        Duck,
+        Goose,
+    }
+
+    struct Barn {
+        remaining: AnimalIter,
+    }
+
+    impl Barn {
+        fn release(&mut self) -> Option<Animal> {
+            self.remaining.next()
+        }
+    }
+
+    #[test]
+    fn variants() {
+        assert_eq!(
+            Animal::variants().collect::<Vec<_>>(),
+            [Animal::Rabbit, Animal::Duck, Animal::Goose]
+        );
+        assert_eq!(
+            Animal::variants().rev().collect::<Vec<_>>(),
+            [Animal::Goose, Animal::Duck, Animal::Rabbit]
+        );
+    }
+
+    #[test]
+    fn stored_iterator() {
+        let mut barn = Barn {
+            remaining: Animal::variants(),
+        };
+        assert_eq!(barn.remaining.len(), 3);
+        assert_eq!(barn.release(), Some(Animal::Rabbit));
+        assert_eq!(barn.remaining.next_back(), Some(Animal::Goose));
+        assert_eq!(barn.remaining.len(), 1);
+        assert_eq!(barn.release(), Some(Animal::Duck));
+        assert_eq!(barn.release(), None);
+        assert_eq!(barn.remaining.next_back(), None);
+    }
+}
+
+mod labels {
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, benzina::Enum)]
+    #[benzina(
+        sql_type = crate::schema::sql_types::Animal,
+        rename_all = "snake_case",
+        labels
+    )]
+    enum Animal {
+        GuineaPig,
+        #[benzina(rename = "hen")]
+        Chicken,
+    }
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, benzina::Enum)]
+    #[benzina(sql_type = crate::schema::sql_types::Animal, rename_all = "snake_case")]
+    enum Unlabeled {
+        GuineaPig,
+        #[benzina(rename = "hen")]
+        Chicken,
+    }
+
+    fn metric_name(label: impl Into<&'static str>) -> String {
+        format!("animals.{}", label.into())
+    }
+
+    #[test]
+    fn into_str_without_labels() {
+        assert_eq!(metric_name(&Unlabeled::GuineaPig), "animals.guinea_pig");
+        assert_eq!(metric_name(&Unlabeled::Chicken), "animals.hen");
+    }
+
+    #[test]
+    fn labels() {
+        assert_eq!(Animal::VARIANTS, ["guinea_pig", "hen"]);
+        assert_eq!(Animal::GuineaPig.as_str(), "guinea_pig");
+        assert_eq!(Animal::Chicken.to_string(), "hen");
+        assert_eq!("hen".parse::<Animal>().unwrap(), Animal::Chicken);
+        assert_eq!(metric_name(&Animal::GuineaPig), "animals.guinea_pig");
+
+        let err = "chicken".parse::<Animal>().unwrap_err();
+        assert_eq!(err.value(), "chicken");
+        assert_eq!(
+            err.to_string(),
+            "unknown variant `chicken`, expected one of `guinea_pig`, `hen`"
+        );
+    }
+}
+
+#[cfg(feature = "postgres")]
+mod canonical_labels {
+    use diesel::{
+        pg::{Pg, PgMetadataLookup, PgTypeMetadata},
+        query_builder::{BindCollector, bind_collector::RawBytesBindCollector},
+    };
+
+    use crate::schema::sql_types::Animal as AnimalType;
+
+    struct FixedLookup;
+
+    impl PgMetadataLookup for FixedLookup {
+        fn lookup_type(&mut self, _type_name: &str, _schema: Option<&str>) -> PgTypeMetadata {
+            PgTypeMetadata::new(1, 2)
+        }
+    }
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, benzina::Enum)]
+    #[benzina(
+        sql_type = crate::schema::sql_types::Animal,
+        rename_all = "kebab-case",
+        labels
+    )]
+    enum Animal {
+        GuineaPig,
+        HTTPServer,
+        #[benzina(rename = "hen")]
+        Chicken,
+    }
+
+    fn to_sql(animal: Animal) -> Vec<u8> {
+        let mut collector = RawBytesBindCollector::<Pg>::new();
+        collector
+            .push_bound_value::<AnimalType, _>(&animal, &mut FixedLookup)
+            .unwrap();
+        collector.binds[0].clone().unwrap()
+    }
+
+    #[test]
+    fn as_str_matches_to_sql() {
+        for animal in [Animal::GuineaPig, Animal::HTTPServer, Animal::Chicken] {
+            assert_eq!(animal.as_str().as_bytes(), to_sql(animal));
+            assert_eq!(animal.to_string().parse::<Animal>().unwrap(), animal);
+        }
+    }
+}
+
+#[cfg(feature = "clap")]
+mod clap {
+    use clap::ValueEnum;
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, benzina::Enum)]
+    #[benzina(
+        sql_type = crate::schema::sql_types::Animal,
+        rename_all = "snake_case",
+        clap
+    )]
+    enum Animal {
+        GuineaPig,
+        #[benzina(rename = "hen")]
+        Chicken,
+    }
+
+    #[test]
+    fn value_enum() {
+        assert_eq!(
+            Animal::value_variants(),
+            [Animal::GuineaPig, Animal::Chicken]
+        );
+        assert_eq!(
+            Animal::GuineaPig.to_possible_value().unwrap().get_name(),
+            "guinea_pig"
+        );
+        assert_eq!(Animal::from_str("hen", false), Ok(Animal::Chicken));
+        assert!(Animal::from_str("chicken", false).is_err());
+    }
+}
+
+#[cfg(feature = "postgres")]
+mod domain {
+    use diesel::{
+        ExpressionMethods, QueryDsl, Queryable, debug_query, deserialize::FromSql, pg::Pg,
+        serialize::ToSql,
+    };
+
+    use crate::schema::{farm, sql_types::FarmAnimal};
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, benzina::Enum)]
+    #[benzina(sql_type = FarmAnimal, rename_all = "snake_case")]
+    enum Animal {
+        Chicken,
+        Duck,
+        #[benzina(rename = "oca")]
+        Goose,
+    }
+
+    #[derive(Debug, Queryable)]
+    #[diesel(table_name = farm, check_for_backend(Pg))]
+    #[expect(dead_code, reason = "only the `Queryable` impl is being tested")]
+    struct Farm {
+        id: i32,
+        animal: Animal,
+    }
+
+    fn assert_sql_impls<T: FromSql<FarmAnimal, Pg> + ToSql<FarmAnimal, Pg>>() {}
+
+    #[test]
+    fn domain_sql_type() {
+        assert_sql_impls::<Animal>();
+
+        let query = farm::table
+            .filter(farm::animal.eq(Animal::Goose))
+            .select(farm::id);
+        assert_eq!(
+            debug_query::<Pg, _>(&query).to_string(),
+            r#"SELECT "farm"."id" FROM "farm" WHERE ("farm"."animal" = $1) -- binds: [Goose]"#
+        );
+    }
+}
+
+#[cfg(feature = "postgres")]
+mod nullable {
+    use diesel::{
+        ExpressionMethods, QueryDsl, Queryable, debug_query, deserialize::FromSqlRow,
+        dsl::count_star, pg::Pg, query_builder::Query,
+    };
+
+    diesel::table! {
+        use diesel::sql_types::*;
+        use crate::schema::sql_types::Animal;
+
+        sightings (id) {
+            id -> Int4,
+            animal -> Nullable<Animal>,
+        }
+    }
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, benzina::Enum)]
+    #[benzina(sql_type = crate::schema::sql_types::Animal, rename_all = "snake_case")]
+    enum Animal {
+        Chicken,
+        Duck,
+    }
+
+    #[derive(Debug, Queryable)]
+    #[diesel(table_name = sightings, check_for_backend(Pg))]
+    #[expect(dead_code, reason = "only the `Queryable` impl is being tested")]
+    struct Sighting {
+        id: i32,
+        animal: Option<Animal>,
+    }
+
+    fn assert_loadable<Q, T>(_query: &Q)
+    where
+        Q: Query,
+        T: FromSqlRow<Q::SqlType, Pg>,
+    {
+    }
+
+    #[test]
+    fn nullable_column() {
+        assert_loadable::<_, Option<Animal>>(&sightings::table.select(sightings::animal));
+        assert_loadable::<_, (Option<Animal>, i64)>(
+            &sightings::table
+                .group_by(sightings::animal)
+                .select((sightings::animal, count_star())),
+        );
+        assert_loadable::<_, Option<Animal>>(
+            &sightings::table
+                .filter(sightings::animal.eq(Animal::Duck))
+                .select(sightings::animal),
+        );
+
+        let rows = vec![
+            sightings::animal.eq(Some(Animal::Chicken)),
+            sightings::animal.eq(None),
+        ];
+        let query = diesel::insert_into(sightings::table).values(&rows);
+        assert_eq!(
+            debug_query::<Pg, _>(&query).to_string(),
+            r#"INSERT INTO "sightings" ("animal") VALUES ($1), ($2) -- binds: [Some(Chicken), None]"#
+        );
+    }
+
+    #[test]
+    fn nullable_from_sql() {
+        use std::num::NonZeroU32;
+
+        use diesel::{deserialize::FromSql, pg::PgValue, sql_types::Nullable};
+
+        use crate::schema::sql_types::Animal as AnimalType;
+
+        let oid = NonZeroU32::new(1).unwrap();
+        let read =
+            <Option<Animal> as FromSql<Nullable<AnimalType>, Pg>>::from_nullable_sql(None).unwrap();
+        assert_eq!(read, None);
+
+        let read = <Option<Animal> as FromSql<Nullable<AnimalType>, Pg>>::from_nullable_sql(Some(
+            PgValue::new(b"duck", &oid),
+        ))
+        .unwrap();
+        assert_eq!(read, Some(Animal::Duck));
+    }
+}
+
+#[cfg(feature = "postgres")]
+mod unknown_error {
+    use std::{error::Error, fmt, num::NonZeroU32};
+
+    use diesel::{
+        deserialize::FromSql,
+        pg::{Pg, PgValue},
+    };
+
+    use crate::schema::sql_types::Animal as AnimalType;
+
+    #[derive(Debug)]
+    struct UnknownAnimal(Vec<u8>);
+
+    impl From<Vec<u8>> for UnknownAnimal {
+        fn from(label: Vec<u8>) -> Self {
+            Self(label)
+        }
+    }
+
+    impl fmt::Display for UnknownAnimal {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "unknown animal {:?}", String::from_utf8_lossy(&self.0))
+        }
+    }
+
+    impl Error for UnknownAnimal {}
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, benzina::Enum)]
+    #[benzina(
+        sql_type = crate::schema::sql_types::Animal,
+        rename_all = "snake_case",
+        unknown_error = UnknownAnimal
+    )]
+    enum Animal {
+        Chicken,
+        Duck,
+    }
+
+    fn from_sql(label: &[u8]) -> diesel::deserialize::Result<Animal> {
+        let oid = NonZeroU32::new(1).unwrap();
+        <Animal as FromSql<AnimalType, Pg>>::from_sql(PgValue::new(label, &oid))
+    }
+
+    #[test]
+    fn unknown_error() {
+        assert_eq!(from_sql(b"duck").unwrap(), Animal::Duck);
+
+        let err = from_sql(b"cat").unwrap_err();
+        assert_eq!(err.to_string(), r#"unknown animal "cat""#);
+        assert_eq!(err.downcast::<UnknownAnimal>().unwrap().0, b"cat");
+    }
+}
+
+#[cfg(feature = "postgres")]
+mod string_sql_type {
+    use diesel::{deserialize::FromSql, pg::Pg, serialize::ToSql};
+
+    use crate::schema::sql_types::Animal as AnimalType;
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, benzina::Enum)]
+    #[benzina(sql_type = "crate::schema::sql_types::Animal")]
+    enum Animal {
+        Chicken,
+        Duck,
+    }
+
+    fn assert_sql_impls<T: FromSql<AnimalType, Pg> + ToSql<AnimalType, Pg>>() {}
+
+    #[test]
+    fn string_sql_type() {
+        assert_sql_impls::<Animal>();
+    }
+}
+
+#[cfg(feature = "postgres")]
+mod defined_sql_type {
+    use diesel::{
+        ExpressionMethods, QueryDsl, debug_query, deserialize::FromSql, pg::Pg, serialize::ToSql,
+    };
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, benzina::Enum)]
+    #[benzina(
+        sql_type = PetKind,
+        define_sql_type = "pet_kind",
+        rename_all = "snake_case"
+    )]
+    pub enum Pet {
+        Cat,
+        GuineaPig,
+    }
+
+    diesel::table! {
+        use diesel::sql_types::*;
+        use super::PetKind;
+
+        pets (id) {
+            id -> Int4,
+            kind -> PetKind,
+        }
+    }
+
+    fn assert_sql_impls<T: FromSql<PetKind, Pg> + ToSql<PetKind, Pg>>() {}
+
+    #[test]
+    fn generated_sql_type() {
+        assert_sql_impls::<Pet>();
+
+        let query = pets::table
+            .filter(pets::kind.eq(Pet::GuineaPig))
+            .select(pets::id);
+        assert_eq!(
+            debug_query::<Pg, _>(&query).to_string(),
+            r#"SELECT "pets"."id" FROM "pets" WHERE ("pets"."kind" = $1) -- binds: [GuineaPig]"#
+        );
+    }
+}
+
+mod transparent {
+    use diesel::{
+        Connection, ExpressionMethods, QueryDsl, RunQueryDsl, SqliteConnection, sql_query,
+    };
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, benzina::Enum)]
+    #[benzina(sql_type = diesel::sql_types::Integer, transparent)]
+    enum Priority {
+        Low,
+        Normal,
+        High = 10,
+        Urgent,
+        Deferred = -1,
+    }
+
+    // too large for `From<&Counter> for i16`, which isn't implemented
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, benzina::Enum)]
+    #[benzina(sql_type = diesel::sql_types::Integer, transparent)]
+    enum Counter {
+        Zero,
+        Large = 100_000,
+    }
+
+    #[test]
+    fn into_i32_only() {
+        assert_eq!(i32::from(&Counter::Zero), 0);
+        assert_eq!(i32::from(&Counter::Large), 100_000);
+    }
+
+    diesel::table! {
+        tasks (id) {
+            id -> Integer,
+            priority -> Integer,
+        }
+    }
+
+    #[test]
+    fn sqlite_round_trip() {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        sql_query("CREATE TABLE tasks (id INTEGER PRIMARY KEY, priority INTEGER NOT NULL)")
+            .execute(&mut conn)
+            .unwrap();
+
+        let priorities = [
+            Priority::Low,
+            Priority::Normal,
+            Priority::High,
+            Priority::Urgent,
+            Priority::Deferred,
+        ];
+        for (id, priority) in (1..).zip(priorities) {
+            diesel::insert_into(tasks::table)
+                .values((tasks::id.eq(id), tasks::priority.eq(priority)))
+                .execute(&mut conn)
+                .unwrap();
+        }
+
+        let raw = tasks::table
+            .select(tasks::priority)
+            .order(tasks::id)
+            .load::<i32>(&mut conn)
+            .unwrap();
+        assert_eq!(raw, [0, 1, 10, 11, -1]);
+        assert_eq!(priorities.iter().map(i32::from).collect::<Vec<_>>(), raw);
+        assert_eq!(
+            priorities.iter().map(i16::from).collect::<Vec<_>>(),
+            [0, 1, 10, 11, -1]
+        );
+
+        let loaded = tasks::table
+            .select(tasks::priority)
+            .order(tasks::id)
+            .load::<Priority>(&mut conn)
+            .unwrap();
+        assert_eq!(loaded, priorities);
+
+        let urgent = tasks::table
+            .select(tasks::priority)
+            .filter(tasks::priority.eq(Priority::Urgent))
+            .first::<Priority>(&mut conn)
+            .unwrap();
+        assert_eq!(urgent, Priority::Urgent);
+
+        sql_query("INSERT INTO tasks (id, priority) VALUES (6, 2)")
+            .execute(&mut conn)
+            .unwrap();
+        let err = tasks::table
+            .select(tasks::priority)
+            .filter(tasks::id.eq(6))
+            .first::<Priority>(&mut conn)
+            .unwrap_err();
+        assert!(err.to_string().contains("Unrecognized enum variant"));
+    }
+}
+
+#[cfg(feature = "array")]
+mod array {
+    use benzina::{Array, ArrayWithNullableItems, NonEmptyVec};
+    use diesel::{
+        ExpressionMethods, debug_query,
+        deserialize::FromSql,
+        pg::Pg,
+        serialize::ToSql,
+        sql_types::{self, Nullable},
+    };
+
+    use crate::schema::sql_types::Animal as AnimalType;
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, benzina::Enum)]
+    #[benzina(sql_type = AnimalType, rename_all = "snake_case")]
+    enum Animal {
+        Cat,
+        GuineaPig,
+    }
+
+    diesel::table! {
+        use diesel::sql_types::*;
+        use crate::schema::sql_types::Animal;
+
+        shelters (id) {
+            id -> Int4,
+            animals -> Array<Nullable<Animal>>,
+        }
+    }
+
+    fn assert_sql_impls<T>()
+    where
+        T: FromSql<sql_types::Array<Nullable<AnimalType>>, Pg>
+            + ToSql<sql_types::Array<Nullable<AnimalType>>, Pg>,
+    {
+    }
+
+    #[test]
+    fn enum_items() {
+        assert_sql_impls::<Array<Animal, 2>>();
+        assert_sql_impls::<ArrayWithNullableItems<Animal, 2>>();
+        assert_sql_impls::<NonEmptyVec<Animal>>();
+
+        let query = diesel::insert_into(shelters::table)
+            .values(shelters::animals.eq(Array::new([Animal::Cat, Animal::GuineaPig])));
+        assert_eq!(
+            debug_query::<Pg, _>(&query).to_string(),
+            r#"INSERT INTO "shelters" ("animals") VALUES ($1) -- binds: [[Cat, GuineaPig]]"#
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use serde_test::{Token, assert_de_tokens_error, assert_tokens};
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, benzina::Enum)]
+    #[benzina(
+        sql_type = crate::schema::sql_types::Animal,
+        rename_all = "snake_case",
+        serde
+    )]
+    enum Animal {
+        Chicken,
+        Duck,
+        #[benzina(rename = "oca")]
+        Goose,
+        Rabbit,
+    }
+
+    #[test]
+    fn round_trip() {
+        assert_tokens(&Animal::Goose, &[Token::Str("oca")]);
+        assert_tokens(&Animal::Chicken, &[Token::Str("chicken")]);
+        assert_tokens(&Animal::Duck, &[Token::Str("duck")]);
+        assert_tokens(&Animal::Rabbit, &[Token::Str("rabbit")]);
+    }
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, benzina::Enum)]
+    #[benzina(
+        sql_type = crate::schema::sql_types::Animal,
+        rename_all = "snake_case",
+        serde
+    )]
+    enum GroupedAcronyms {
+        HTTPError,
+        ServerHTTP,
+    }
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, benzina::Enum)]
+    #[benzina(
+        sql_type = crate::schema::sql_types::Animal,
+        rename_all = "snake_case",
+        acronyms = "split",
+        serde
+    )]
+    enum SplitAcronyms {
+        HTTPError,
+        ServerHTTP,
+    }
+
+    #[test]
+    fn acronyms() {
+        assert_tokens(&GroupedAcronyms::HTTPError, &[Token::Str("http_error")]);
+        assert_tokens(&GroupedAcronyms::ServerHTTP, &[Token::Str("server_http")]);
+        assert_tokens(&SplitAcronyms::HTTPError, &[Token::Str("h_t_t_p_error")]);
+        assert_tokens(&SplitAcronyms::ServerHTTP, &[Token::Str("server_h_t_t_p")]);
+    }
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, benzina::Enum)]
+    #[benzina(
+        sql_type = crate::schema::sql_types::Animal,
+        rename_all(pg = "kebab-case", mysql = "UPPERCASE"),
+        serde
+    )]
+    enum PerBackend {
+        GuineaPig,
+    }
+
+    #[test]
+    fn per_backend_rename_all() {
+        // serde follows the `pg` rule whatever the enabled backends
+        assert_tokens(&PerBackend::GuineaPig, &[Token::Str("guinea-pig")]);
+    }
+
+    #[cfg(feature = "mysql")]
+    #[test]
+    fn per_backend_mysql_labels() {
+        use diesel::{
+            deserialize::FromSql,
+            mysql::{Mysql, MysqlType, MysqlValue},
+            query_builder::{BindCollector, bind_collector::RawBytesBindCollector},
+        };
+
+        use crate::schema::sql_types::Animal as AnimalType;
+
+        let mut collector = RawBytesBindCollector::<Mysql>::new();
+        collector
+            .push_bound_value::<AnimalType, _>(&PerBackend::GuineaPig, &mut ())
+            .unwrap();
+        assert_eq!(collector.binds[0].as_deref(), Some(&b"GUINEAPIG"[..]));
+
+        let read = <PerBackend as FromSql<AnimalType, Mysql>>::from_sql(MysqlValue::new(
+            b"GUINEAPIG",
+            MysqlType::Enum,
+        ))
+        .unwrap();
+        assert_eq!(read, PerBackend::GuineaPig);
+    }
+
+    #[test]
+    fn unknown_variant() {
+        assert_de_tokens_error::<Animal>(
+            &[Token::Str("goose")],
+            "unknown variant `goose`, expected one of `chicken`, `duck`, `oca`, `rabbit`",
+        );
+    }
+}
@@ -0,0 +1,897 @@
+#![cfg(feature = "derive")]
+
+use std::hash::{BuildHasherDefault, DefaultHasher};
+
+use diesel::{Identifiable, QueryResult};
+use indexmap::IndexMap;
+
+diesel::table! {
+    users {
+        id -> Integer,
+        name -> Text,
+    }
+}
+
+diesel::table! {
+    posts {
+        id -> Integer,
+        user_id -> Integer,
+        message -> Text,
+    }
+}
+
+diesel::table! {
+    comments {
+        id -> Integer,
+        post_id -> Integer,
+        message -> Text,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Identifiable)]
+#[diesel(table_name = users)]
+struct User {
+    id: i32,
+    name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Identifiable)]
+#[diesel(table_name = posts)]
+struct Post {
+    id: i32,
+    user_id: i32,
+    message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Identifiable)]
+#[diesel(table_name = comments)]
+struct Comment {
+    id: i32,
+    post_id: i32,
+    message: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct UserWithPosts {
+    user: User,
+    posts: Vec<Post>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct UserWithCommentedPosts {
+    user: User,
+    posts: Vec<PostWithComments>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct UserWithPost {
+    user: User,
+    post: Post,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct UserPosts(User, Vec<Post>);
+
+#[derive(Debug, PartialEq, Eq)]
+struct PostWithComments {
+    post: Post,
+    comments: Vec<Comment>,
+}
+
+fn user(id: i32, name: &str) -> User {
+    User {
+        id,
+        name: name.to_owned(),
+    }
+}
+
+fn post(id: i32, user_id: i32, message: &str) -> Post {
+    Post {
+        id,
+        user_id,
+        message: message.to_owned(),
+    }
+}
+
+fn comment(id: i32, post_id: i32, message: &str) -> Comment {
+    Comment {
+        id,
+        post_id,
+        message: message.to_owned(),
+    }
+}
+
+#[test]
+fn users_with_posts() {
+    let records = vec![
+        (user(1, "alice"), Some(post(1, 1, "hello"))),
+        (user(2, "bob"), None),
+        (user(1, "alice"), Some(post(2, 1, "world"))),
+        (user(3, "carol"), Some(post(3, 3, "hi"))),
+    ];
+
+    let joined = benzina::join! {
+        records,
+        Vec<UserWithPosts {
+            user: One<0>,
+            posts: Vec0<1>,
+        }>,
+    };
+
+    assert_eq!(
+        joined,
+        vec![
+            UserWithPosts {
+                user: user(1, "alice"),
+                posts: vec![post(1, 1, "hello"), post(2, 1, "world")],
+            },
+            UserWithPosts {
+                user: user(2, "bob"),
+                posts: vec![],
+            },
+            UserWithPosts {
+                user: user(3, "carol"),
+                posts: vec![post(3, 3, "hi")],
+            },
+        ]
+    );
+}
+
+#[test]
+fn tuple_struct_users_with_posts() {
+    let records = vec![
+        (user(1, "alice"), Some(post(1, 1, "hello"))),
+        (user(2, "bob"), None),
+        (user(1, "alice"), Some(post(2, 1, "world"))),
+    ];
+
+    let joined = benzina::join! {
+        records,
+        Vec<UserPosts {
+            0: One<0>,
+            1: Vec0<1>,
+        }>,
+    };
+
+    assert_eq!(
+        joined,
+        vec![
+            UserPosts(
+                user(1, "alice"),
+                vec![post(1, 1, "hello"), post(2, 1, "world")]
+            ),
+            UserPosts(user(2, "bob"), vec![]),
+        ]
+    );
+}
+
+#[test]
+#[expect(
+    clippy::unnecessary_wraps,
+    reason = "`join!` needs to be called from a function returning a `QueryResult`"
+)]
+fn users_with_commented_posts() -> QueryResult<()> {
+    let records = vec![
+        (
+            user(1, "alice"),
+            Some(post(1, 1, "hello")),
+            Some(comment(1, 1, "first")),
+        ),
+        (
+            user(1, "alice"),
+            Some(post(1, 1, "hello")),
+            Some(comment(2, 1, "second")),
+        ),
+        (user(1, "alice"), Some(post(2, 1, "world")), None),
+        (user(2, "bob"), None, None),
+    ];
+
+    let joined = benzina::join! {
+        records,
+        Vec<UserWithCommentedPosts {
+            user: One<0>,
+            posts: Vec0<PostWithComments {
+                post: One<1>,
+                comments: Vec0<2>,
+            }>,
+        }>,
+    };
+
+    assert_eq!(
+        joined,
+        vec![
+            UserWithCommentedPosts {
+                user: user(1, "alice"),
+                posts: vec![
+                    PostWithComments {
+                        post: post(1, 1, "hello"),
+                        comments: vec![comment(1, 1, "first"), comment(2, 1, "second")],
+                    },
+                    PostWithComments {
+                        post: post(2, 1, "world"),
+                        comments: vec![],
+                    },
+                ],
+            },
+            UserWithCommentedPosts {
+                user: user(2, "bob"),
+                posts: vec![],
+            },
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn users_with_required_posts() {
+    let records = vec![
+        (user(1, "alice"), post(1, 1, "hello")),
+        (user(3, "carol"), post(3, 3, "hi")),
+        (user(1, "alice"), post(2, 1, "world")),
+        (user(1, "alice"), post(2, 1, "world")),
+    ];
+
+    let joined = benzina::join! {
+        records,
+        Vec<UserWithPosts {
+            user: One<0>,
+            posts: Vec<1>,
+        }>,
+    };
+
+    assert_eq!(
+        joined,
+        vec![
+            UserWithPosts {
+                user: user(1, "alice"),
+                posts: vec![post(1, 1, "hello"), post(2, 1, "world")],
+            },
+            UserWithPosts {
+                user: user(3, "carol"),
+                posts: vec![post(3, 3, "hi")],
+            },
+        ]
+    );
+}
+
+#[test]
+fn user_with_assumed_post() -> QueryResult<()> {
+    let records = vec![(user(1, "alice"), Some(post(1, 1, "hello")))];
+
+    let joined = benzina::join! {
+        records,
+        One<UserWithPost {
+            user: One<0>,
+            post: AssumeOne<1>,
+        }>,
+    };
+    assert_eq!(
+        joined,
+        UserWithPost {
+            user: user(1, "alice"),
+            post: post(1, 1, "hello"),
+        }
+    );
+
+    let nested_records = vec![
+        (user(1, "alice"), Some(post(1, 1, "hello"))),
+        (user(2, "bob"), Some(post(2, 2, "world"))),
+    ];
+    let joined = benzina::join! {
+        nested_records,
+        Vec<UserWithPost {
+            user: One<0>,
+            post: AssumeOne<1>,
+        }>,
+    };
+    assert_eq!(joined.len(), 2);
+    Ok(())
+}
+
+#[test]
+fn user_with_missing_assumed_post() {
+    fn join(records: Vec<(User, Option<Post>)>) -> QueryResult<Vec<UserWithPost>> {
+        Ok(benzina::join! {
+            records,
+            Vec<UserWithPost {
+                user: One<0>,
+                post: AssumeOne<1>,
+            }>,
+        })
+    }
+
+    assert!(matches!(
+        join(vec![(user(1, "alice"), None)]),
+        Err(diesel::result::Error::DeserializationError(_))
+    ));
+}
+
+#[test]
+fn first_seen_order() {
+    // scatter the ids so that any hash-based ordering would show up
+    let ids = (0..100).map(|n| (n * 37) % 101).collect::<Vec<i32>>();
+    let records = ids
+        .iter()
+        .chain(ids.iter().rev())
+        .map(|&id| (user(id, "user"), Some(post(id, id, "post"))))
+        .collect::<Vec<_>>();
+
+    let joined = benzina::join! {
+        records,
+        Vec<UserWithPosts {
+            user: One<0>,
+            posts: Vec0<1>,
+        }>,
+    };
+
+    assert_eq!(
+        joined
+            .iter()
+            .map(|user_with_posts| user_with_posts.user.id)
+            .collect::<Vec<_>>(),
+        ids
+    );
+}
+
+#[test]
+fn streamed_users_with_posts() {
+    let records = vec![
+        (user(1, "alice"), Some(post(1, 1, "hello"))),
+        (user(2, "bob"), None),
+        (user(1, "alice"), Some(post(2, 1, "world"))),
+    ];
+    let streamed_records = records.clone();
+
+    let joined = benzina::join! {
+        records,
+        Vec<UserWithPosts {
+            user: One<0>,
+            posts: Vec0<1>,
+        }>,
+    };
+    let mut streamed = benzina::join_iter! {
+        streamed_records,
+        Vec<UserWithPosts {
+            user: One<0>,
+            posts: Vec0<1>,
+        }>,
+    };
+
+    assert_eq!(streamed.len(), 2);
+    assert_eq!(streamed.next().as_ref(), joined.first());
+    assert_eq!(streamed.collect::<Vec<_>>(), joined[1..]);
+}
+
+#[test]
+fn streamed_users_with_commented_posts() {
+    let records = vec![
+        (
+            user(1, "alice"),
+            Some(post(1, 1, "hello")),
+            Some(comment(1, 1, "first")),
+        ),
+        (user(2, "bob"), None, None),
+    ];
+
+    let streamed = benzina::join_iter! {
+        records,
+        Vec<UserWithCommentedPosts {
+            user: One<0>,
+            posts: Vec0<PostWithComments {
+                post: One<1>,
+                comments: Vec0<2>,
+            }>,
+        }>,
+    };
+
+    assert_eq!(
+        streamed.collect::<Vec<_>>(),
+        vec![
+            UserWithCommentedPosts {
+                user: user(1, "alice"),
+                posts: vec![PostWithComments {
+                    post: post(1, 1, "hello"),
+                    comments: vec![comment(1, 1, "first")],
+                }],
+            },
+            UserWithCommentedPosts {
+                user: user(2, "bob"),
+                posts: vec![],
+            },
+        ]
+    );
+}
+
+#[test]
+fn grouped_users_with_commented_posts() {
+    let records = vec![
+        (
+            user(1, "alice"),
+            Some(post(1, 1, "hello")),
+            Some(comment(1, 1, "first")),
+        ),
+        (user(2, "bob"), None, None),
+        (
+            user(1, "alice"),
+            Some(post(1, 1, "hello")),
+            Some(comment(2, 1, "second")),
+        ),
+        (user(1, "alice"), Some(post(2, 1, "world")), None),
+    ];
+
+    let grouped = benzina::join_map! {
+        records,
+        Vec<UserWithCommentedPosts {
+            user: One<0>,
+            posts: Vec0<PostWithComments {
+                post: One<1>,
+                comments: Vec0<2>,
+            }>,
+        }>,
+    };
+
+    assert_eq!(grouped.keys().collect::<Vec<_>>(), [&(1,), &(2,)]);
+    let comment_counts = grouped
+        .into_values()
+        .map(|(user, posts)| {
+            let comments = posts
+                .into_values()
+                .map(|(_post, comments)| comments.len())
+                .sum::<usize>();
+            (user.name, comments)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        comment_counts,
+        [("alice".to_owned(), 2), ("bob".to_owned(), 0)]
+    );
+}
+
+#[test]
+fn sorted_users_with_posts() {
+    let records = vec![
+        (user(1, "alice"), Some(post(1, 1, "hello"))),
+        (user(1, "alice"), Some(post(2, 1, "world"))),
+        (user(2, "bob"), None),
+        (user(3, "carol"), Some(post(3, 3, "hi"))),
+    ];
+    let sorted_records = records.clone();
+
+    let joined = benzina::join! {
+        records,
+        Vec<UserWithPosts {
+            user: One<0>,
+            posts: Vec0<1>,
+        }>,
+    };
+    let sorted = benzina::join_sorted! {
+        sorted_records,
+        Vec<UserWithPosts {
+            user: One<0>,
+            posts: Vec0<1>,
+        }>,
+    };
+
+    assert_eq!(sorted, joined);
+    assert_eq!(sorted.len(), 3);
+}
+
+#[test]
+#[expect(
+    clippy::unnecessary_wraps,
+    reason = "`join_sorted!` needs to be called from a function returning a `QueryResult`"
+)]
+fn sorted_users_with_commented_posts() -> QueryResult<()> {
+    // only the users are grouped, the posts and comments are interleaved
+    let records = vec![
+        (
+            user(1, "alice"),
+            Some(post(1, 1, "hello")),
+            Some(comment(1, 1, "first")),
+        ),
+        (user(1, "alice"), Some(post(2, 1, "world")), None),
+        (
+            user(1, "alice"),
+            Some(post(1, 1, "hello")),
+            Some(comment(2, 1, "second")),
+        ),
+        (user(2, "bob"), None, None),
+    ];
+
+    let joined = benzina::join_sorted! {
+        records,
+        Vec<UserWithCommentedPosts {
+            user: One<0>,
+            posts: Vec0<PostWithComments {
+                post: One<1>,
+                comments: Vec0<2>,
+            }>,
+        }>,
+    };
+
+    assert_eq!(
+        joined,
+        vec![
+            UserWithCommentedPosts {
+                user: user(1, "alice"),
+                posts: vec![
+                    PostWithComments {
+                        post: post(1, 1, "hello"),
+                        comments: vec![comment(1, 1, "first"), comment(2, 1, "second")],
+                    },
+                    PostWithComments {
+                        post: post(2, 1, "world"),
+                        comments: vec![],
+                    },
+                ],
+            },
+            UserWithCommentedPosts {
+                user: user(2, "bob"),
+                posts: vec![],
+            },
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn sorted_duplicates_ungrouped_rows() {
+    let records = vec![
+        (user(1, "alice"), Some(post(1, 1, "hello"))),
+        (user(2, "bob"), None),
+        (user(1, "alice"), Some(post(2, 1, "world"))),
+    ];
+
+    let joined = benzina::join_sorted! {
+        records,
+        Vec<UserWithPosts {
+            user: One<0>,
+            posts: Vec0<1>,
+        }>,
+    };
+
+    assert_eq!(joined.len(), 3);
+    assert_eq!(joined[0].posts, vec![post(1, 1, "hello")]);
+    assert_eq!(joined[2].posts, vec![post(2, 1, "world")]);
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct PostWithAuthorAndEditor {
+    post: Post,
+    author: User,
+    editor: User,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct UserWithAuthoredPosts {
+    user: User,
+    posts: Vec<PostWithAuthor>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct PostWithAuthor {
+    post: Post,
+    author: User,
+}
+
+#[test]
+fn shared_tuple_index() {
+    let records = vec![
+        (post(1, 1, "hello"), user(1, "alice")),
+        (post(2, 2, "world"), user(2, "bob")),
+    ];
+
+    let joined = benzina::join! {
+        records,
+        Vec<PostWithAuthorAndEditor {
+            post: One<0>,
+            author: One<1>,
+            editor: One<1>,
+        }>,
+    };
+
+    assert_eq!(
+        joined,
+        vec![
+            PostWithAuthorAndEditor {
+                post: post(1, 1, "hello"),
+                author: user(1, "alice"),
+                editor: user(1, "alice"),
+            },
+            PostWithAuthorAndEditor {
+                post: post(2, 2, "world"),
+                author: user(2, "bob"),
+                editor: user(2, "bob"),
+            },
+        ]
+    );
+}
+
+#[test]
+fn shared_tuple_index_in_nested_structure() {
+    let records = vec![
+        (user(1, "alice"), Some(post(1, 1, "hello"))),
+        (user(2, "bob"), None),
+        (user(1, "alice"), Some(post(2, 1, "world"))),
+    ];
+
+    let joined = benzina::join! {
+        records,
+        Vec<UserWithAuthoredPosts {
+            user: One<0>,
+            posts: Vec0<PostWithAuthor {
+                post: One<1>,
+                author: One<0>,
+            }>,
+        }>,
+    };
+
+    assert_eq!(
+        joined,
+        vec![
+            UserWithAuthoredPosts {
+                user: user(1, "alice"),
+                posts: vec![
+                    PostWithAuthor {
+                        post: post(1, 1, "hello"),
+                        author: user(1, "alice"),
+                    },
+                    PostWithAuthor {
+                        post: post(2, 1, "world"),
+                        author: user(1, "alice"),
+                    },
+                ],
+            },
+            UserWithAuthoredPosts {
+                user: user(2, "bob"),
+                posts: vec![],
+            },
+        ]
+    );
+}
+
+#[derive(Debug, PartialEq, Eq, Identifiable)]
+#[diesel(table_name = users)]
+struct Author {
+    id: i32,
+    name: String,
+    posts: Vec<Post>,
+}
+
+fn author(id: i32, name: &str, posts: Vec<Post>) -> Author {
+    Author {
+        id,
+        name: name.to_owned(),
+        posts,
+    }
+}
+
+#[test]
+fn struct_base() {
+    let records = vec![
+        (author(1, "alice", vec![]), Some(post(1, 1, "hello"))),
+        (author(2, "bob", vec![]), None),
+        (author(1, "alice", vec![]), Some(post(2, 1, "world"))),
+    ];
+
+    let joined = benzina::join! {
+        records,
+        Vec<Author {
+            posts: Vec0<1>,
+            ..One<0>
+        }>,
+    };
+
+    assert_eq!(
+        joined,
+        vec![
+            author(1, "alice", vec![post(1, 1, "hello"), post(2, 1, "world")]),
+            author(2, "bob", vec![]),
+        ]
+    );
+}
+
+#[test]
+fn custom_hasher() {
+    type DeterministicHasher = BuildHasherDefault<DefaultHasher>;
+
+    let records = vec![
+        (user(1, "alice"), Some(post(1, 1, "hello"))),
+        (user(2, "bob"), None),
+        (user(1, "alice"), Some(post(2, 1, "world"))),
+    ];
+
+    let cloned_records = records.clone();
+    let joined = benzina::join! {
+        cloned_records,
+        hasher = DeterministicHasher,
+        Vec<UserWithPosts {
+            user: One<0>,
+            posts: Vec0<1>,
+        }>,
+    };
+    assert_eq!(
+        joined,
+        vec![
+            UserWithPosts {
+                user: user(1, "alice"),
+                posts: vec![post(1, 1, "hello"), post(2, 1, "world")],
+            },
+            UserWithPosts {
+                user: user(2, "bob"),
+                posts: vec![],
+            },
+        ]
+    );
+
+    let grouped: IndexMap<_, (User, IndexMap<_, Post, DeterministicHasher>), DeterministicHasher> = benzina::join_map! {
+        records,
+        hasher = DeterministicHasher,
+        Vec<UserWithPosts {
+            user: One<0>,
+            posts: Vec0<1>,
+        }>,
+    };
+    assert_eq!(grouped.len(), 2);
+}
+
+diesel::table! {
+    memberships (user_id, group_id) {
+        user_id -> Integer,
+        group_id -> Integer,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Identifiable)]
+#[diesel(table_name = memberships, primary_key(user_id, group_id))]
+struct Membership {
+    user_id: i32,
+    group_id: i32,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct MembershipWithPosts {
+    membership: Membership,
+    posts: Vec<Post>,
+}
+
+#[test]
+fn composite_key() {
+    let membership = |user_id, group_id| Membership { user_id, group_id };
+    let records = vec![
+        (membership(1, 1), Some(post(1, 1, "hello"))),
+        (membership(1, 2), Some(post(2, 1, "world"))),
+        (membership(2, 1), None),
+        (membership(1, 1), Some(post(3, 1, "again"))),
+    ];
+
+    let joined = benzina::join! {
+        records,
+        Vec<MembershipWithPosts {
+            membership: One<0>,
+            posts: Vec0<1>,
+        }>,
+    };
+
+    assert_eq!(
+        joined,
+        vec![
+            MembershipWithPosts {
+                membership: membership(1, 1),
+                posts: vec![post(1, 1, "hello"), post(3, 1, "again")],
+            },
+            MembershipWithPosts {
+                membership: membership(1, 2),
+                posts: vec![post(2, 1, "world")],
+            },
+            MembershipWithPosts {
+                membership: membership(2, 1),
+                posts: vec![],
+            },
+        ]
+    );
+}
+
+mod models {
+    use super::User;
+
+    #[derive(Debug, PartialEq, Eq)]
+    pub(crate) struct UserWith<T> {
+        pub(crate) user: User,
+        pub(crate) items: Vec<T>,
+    }
+}
+
+#[test]
+fn qualified_output_type() {
+    let records = vec![
+        (user(1, "alice"), Some(post(1, 1, "hello"))),
+        (user(2, "bob"), None),
+        (user(1, "alice"), Some(post(2, 1, "world"))),
+    ];
+
+    let joined = benzina::join! {
+        records,
+        Vec<models::UserWith<Post> {
+            user: One<0>,
+            items: Vec0<1>,
+        }>,
+    };
+
+    assert_eq!(
+        joined,
+        vec![
+            models::UserWith {
+                user: user(1, "alice"),
+                items: vec![post(1, 1, "hello"), post(2, 1, "world")],
+            },
+            models::UserWith {
+                user: user(2, "bob"),
+                items: vec![],
+            },
+        ]
+    );
+}
+
+mod reexport {
+    pub(crate) use benzina as inner;
+}
+
+/// Like a macro exported by a crate re-exporting benzina
+macro_rules! users_with_posts {
+    ($records:ident) => {
+        benzina::join! {
+            $records,
+            crate = $crate::reexport::inner,
+            Vec<UserWithPosts {
+                user: One<0>,
+                posts: Vec0<1>,
+            }>,
+        }
+    };
+}
+
+#[test]
+fn renamed_crate() {
+    let records = vec![
+        (user(1, "alice"), Some(post(1, 1, "hello"))),
+        (user(2, "bob"), None),
+    ];
+
+    let joined = users_with_posts!(records);
+    assert_eq!(
+        joined,
+        vec![
+            UserWithPosts {
+                user: user(1, "alice"),
+                posts: vec![post(1, 1, "hello")],
+            },
+            UserWithPosts {
+                user: user(2, "bob"),
+                posts: vec![],
+            },
+        ]
+    );
+}
+
+#[test]
+fn group_children_flat_rows() {
+    let posts = vec![
+        post(1, 2, "hello"),
+        post(2, 1, "world"),
+        post(3, 2, "again"),
+    ];
+
+    let grouped = benzina::group_children!(
+        posts,
+        key = |post| post.user_id,
+        child = |post| post.message,
+    );
+    assert_eq!(
+        grouped,
+        vec![
+            (2, vec!["hello".to_owned(), "again".to_owned()]),
+            (1, vec!["world".to_owned()]),
+        ]
+    );
+}